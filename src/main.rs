@@ -1,11 +1,40 @@
 extern crate chrono;
+use std::env;
 use std::io;
 
 // Import crates/functions.
 pub mod standings;
 
+const ARCHIVE_FILE: &str = "archive.txt";
+const COMPETITIONS_FILE: &str = "competitions.txt";
+const SQUADS_FILE: &str = "squads.txt";
+
 enum Commands {
     PrintTable,
+    PrintTableAfterRound,
+    PrintTableOnDate,
+    ListMatchesBetweenDates,
+    PrintVenueTable,
+    PrintFormTable,
+    SavePositionHistory,
+    SimulateSeason,
+    PrintRatings,
+    PredictMatch,
+    Scenarios,
+    PrintProjectedTable,
+    PrintSeasonStats,
+    PrintHeadToHead,
+    PrintAllTimeTable,
+    CompareSeasons,
+    SeasonRollover,
+    SelectDivision,
+    PlayTie,
+    Cup,
+    Tournament,
+    PrintSplitTables,
+    Fixtures,
+    PrintTopScorers,
+    PrintSquad,
     AddResult,
     ReadResultFile,
     ReadTableFile,
@@ -21,7 +50,31 @@ impl Commands {
             "3" => Some(Commands::ReadResultFile),
             "4" => Some(Commands::ReadTableFile),
             "5" => Some(Commands::SaveTableToFile),
-            "6" => Some(Commands::Exit),
+            "6" => Some(Commands::PrintTableAfterRound),
+            "7" => Some(Commands::PrintTableOnDate),
+            "8" => Some(Commands::ListMatchesBetweenDates),
+            "9" => Some(Commands::PrintVenueTable),
+            "10" => Some(Commands::PrintFormTable),
+            "11" => Some(Commands::SavePositionHistory),
+            "12" => Some(Commands::SimulateSeason),
+            "13" => Some(Commands::PrintRatings),
+            "14" => Some(Commands::PredictMatch),
+            "15" => Some(Commands::Scenarios),
+            "16" => Some(Commands::PrintProjectedTable),
+            "17" => Some(Commands::PrintSeasonStats),
+            "18" => Some(Commands::PrintHeadToHead),
+            "19" => Some(Commands::PrintAllTimeTable),
+            "20" => Some(Commands::CompareSeasons),
+            "21" => Some(Commands::SeasonRollover),
+            "22" => Some(Commands::SelectDivision),
+            "23" => Some(Commands::PlayTie),
+            "24" => Some(Commands::Cup),
+            "25" => Some(Commands::Tournament),
+            "26" => Some(Commands::PrintSplitTables),
+            "27" => Some(Commands::Fixtures),
+            "28" => Some(Commands::PrintTopScorers),
+            "29" => Some(Commands::PrintSquad),
+            "30" => Some(Commands::Exit),
             _ => None,
         }
     }
@@ -47,38 +100,182 @@ fn main() {
         String::from("Värnamo"),
         String::from("Sirius"),
     ];
-    let mut table: standings::tables::Table = standings::read_table_from_file("table.txt");
+    let competitions =
+        standings::read_competitions_from_file(COMPETITIONS_FILE).unwrap_or_default();
+    let mut league = standings::read_league(competitions, "table.txt");
+    if league.table().teams.is_empty() {
+        league.table_mut().teams = standings::tables::create_table(&teams).teams;
+    }
+    for (index, division) in league.divisions.iter_mut().enumerate() {
+        let name = &division.competition.name;
+        if let Ok(schedule) =
+            standings::read_fixtures_from_file(&division_file("fixtures", index, name))
+        {
+            division.table.fixtures = schedule;
+        }
+        if let Ok(ratings) =
+            standings::read_ratings_from_file(&division_file("ratings", index, name))
+        {
+            division.table.ratings = ratings;
+        }
+    }
+    league.squads = standings::read_squads_from_file(SQUADS_FILE).unwrap_or_default();
+    if let Some(name) = division_argument() {
+        if let Err(error) = league.select(&name) {
+            println!("{}: {}", name, error);
+        }
+    }
+
+    // Without an archive file there is simply no history, any other error is reported.
+    let archive = standings::read_archive_from_file(ARCHIVE_FILE).unwrap_or_else(|error| {
+        if error.kind() != io::ErrorKind::NotFound {
+            println!("Could not read {}: {}", ARCHIVE_FILE, error);
+        }
+        Default::default()
+    });
 
+    let mut scenarios: Vec<standings::scenario::Scenario> = Vec::new();
+    let mut cup: Option<standings::cup::Cup> = None;
+    let mut tournament: Option<standings::tournament::Tournament> = None;
     let mut inputs = String::new();
 
     loop {
-        println!("Choose your option!");
+        println!(
+            "Choose your option! ({})",
+            league.division().competition.name
+        );
         println!("1. Print table.");
         println!("2. Add new result.");
         println!("3. Read multiple results from file.");
         println!("4. Read table from file.");
         println!("5. Save current table.");
-        println!("6. Exit.");
+        println!("6. Print table after round.");
+        println!("7. Print table on date.");
+        println!("8. List matches between dates.");
+        println!("9. Print home or away table.");
+        println!("10. Print form table.");
+        println!("11. Export position history and bump chart.");
+        println!("12. Simulate the rest of the season.");
+        println!("13. Print Elo ratings.");
+        println!("14. Predict a match.");
+        println!("15. What-if scenarios.");
+        println!("16. Print points per game and projected table.");
+        println!("17. Print season statistics.");
+        println!("18. Print head-to-head record.");
+        println!("19. Print all-time table.");
+        println!("20. Compare seasons.");
+        println!("21. Roll over to next season.");
+        println!("22. Select division.");
+        println!("23. Play a knockout tie.");
+        println!("24. Knockout cup.");
+        println!("25. Group stage tournament.");
+        println!("26. Print split tables.");
+        println!("27. Fixtures and postponements.");
+        println!("28. Print top scorers.");
+        println!("29. Print squad.");
+        println!("30. Exit.");
 
         inputs.clear();
-        table.update_table();
+        league.table_mut().update_table();
 
-        if let Ok(_) = io::stdin().read_line(&mut inputs) {
+        if io::stdin().read_line(&mut inputs).is_ok() {
             inputs.pop();
 
             let command = Commands::from_string(&inputs);
 
             match command {
-                Some(Commands::PrintTable) => table.print(),
-                Some(Commands::AddResult) => standings::add_result(&mut table),
+                Some(Commands::PrintTable) => league.table_mut().print_standings(),
+                Some(Commands::AddResult) => standings::add_result(&mut league),
                 Some(Commands::ReadResultFile) => {
-                    standings::read_result_from_file(&mut table).unwrap()
+                    standings::read_result_from_file(&mut league).unwrap()
+                }
+                Some(Commands::ReadTableFile) => {
+                    let table = league.table_mut();
+                    let loaded = standings::read_table_from_input_file();
+                    table.teams = loaded.teams;
+                    table.matches = loaded.matches;
+                }
+                Some(Commands::SaveTableToFile) => {
+                    standings::save_table_to_file(league.table()).unwrap();
+                    save_ratings(&league);
+                }
+                Some(Commands::PrintTableAfterRound) => {
+                    standings::print_table_after_round(league.table())
+                }
+                Some(Commands::PrintTableOnDate) => standings::print_table_on_date(league.table()),
+                Some(Commands::ListMatchesBetweenDates) => {
+                    standings::list_matches_between_dates(league.table())
+                }
+                Some(Commands::PrintVenueTable) => {
+                    standings::print_venue_table(league.table()).unwrap()
+                }
+                Some(Commands::PrintFormTable) => standings::print_form_table(league.table()),
+                Some(Commands::SavePositionHistory) => {
+                    standings::save_position_history(league.table()).unwrap()
+                }
+                Some(Commands::SimulateSeason) => standings::simulate_season(league.table()),
+                Some(Commands::PrintRatings) => standings::print_ratings(league.table()),
+                Some(Commands::PredictMatch) => standings::predict_match(league.table()),
+                Some(Commands::Scenarios) => {
+                    standings::scenario_menu(league.table(), &mut scenarios)
+                }
+                Some(Commands::PrintProjectedTable) => {
+                    standings::print_projected_table(league.table())
+                }
+                Some(Commands::PrintSeasonStats) => {
+                    standings::print_season_stats(league.table()).unwrap()
+                }
+                Some(Commands::PrintHeadToHead) => {
+                    standings::print_head_to_head(&archive, league.table())
+                }
+                Some(Commands::PrintAllTimeTable) => {
+                    standings::print_all_time_table(&archive, league.table())
+                }
+                Some(Commands::CompareSeasons) => {
+                    standings::print_season_comparison(&archive, league.table())
+                }
+                Some(Commands::SeasonRollover) => standings::season_rollover(&league),
+                Some(Commands::SelectDivision) => {
+                    standings::select_division(&mut league);
+                    scenarios.clear();
+                }
+                Some(Commands::PlayTie) => standings::play_tie(&league),
+                Some(Commands::Cup) => standings::cup_menu(&league.registry(), &mut cup),
+                Some(Commands::Tournament) => standings::tournament_menu(&mut tournament),
+                Some(Commands::PrintSplitTables) => standings::print_split_tables(&league),
+                Some(Commands::Fixtures) => standings::fixtures_menu(league.table_mut()),
+                Some(Commands::PrintTopScorers) => standings::print_top_scorers(league.table()),
+                Some(Commands::PrintSquad) => standings::print_squad(&league),
+                Some(Commands::Exit) => {
+                    save_ratings(&league);
+                    return;
                 }
-                Some(Commands::ReadTableFile) => table = standings::read_table_from_input_file(),
-                Some(Commands::SaveTableToFile) => standings::save_table_to_file(&table).unwrap(),
-                Some(Commands::Exit) => return,
                 None => println!("Unknown command."),
             }
         }
     }
 }
+
+// Schedules and ratings of the top division are in "<base>.txt", those of the other divisions
+// in "<base>_<division>.txt".
+fn division_file(base: &str, index: usize, division_name: &str) -> String {
+    if index == 0 {
+        format!("{}.txt", base)
+    } else {
+        format!("{}_{}.txt", base, division_name.to_lowercase())
+    }
+}
+
+fn save_ratings(league: &standings::league::League) {
+    for (index, division) in league.divisions.iter().enumerate() {
+        let file_name = division_file("ratings", index, &division.competition.name);
+        standings::save_ratings_to_file(&division.table.ratings, &file_name).unwrap();
+    }
+}
+
+// The division to start in, given as "--division <name>".
+fn division_argument() -> Option<String> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|arg| arg == "--division")?;
+    args.get(index + 1).cloned()
+}
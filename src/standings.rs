@@ -6,10 +6,37 @@ use std::io::BufRead;
 use std::io::BufReader;
 use std::io::Write;
 
+pub mod archive;
+pub mod calendar;
+pub mod cards;
+pub mod clinch;
+pub mod comparison;
+pub mod competition;
+pub mod cup;
+pub mod elo;
+pub mod fixtures;
+pub mod head_to_head;
+pub mod history;
+pub mod league;
+pub mod matches;
+pub mod prediction;
+pub mod projection;
+pub mod random;
+pub mod scenario;
+pub mod scorers;
+pub mod simulation;
+pub mod split;
+pub mod squads;
+pub mod stats;
 pub mod tables;
 pub mod team;
+pub mod tie;
+pub mod tournament;
+pub mod zones;
 
-fn parse_teams(teams_str: &String) -> Result<Vec<String>, &'static str> {
+type ParsedTeams = Vec<Result<team::TeamStats, &'static str>>;
+
+fn parse_teams(teams_str: &str) -> Result<Vec<String>, &'static str> {
     let teams: Vec<&str> = teams_str.split(',').collect();
     if teams.is_empty() {
         return Err("No teams provided.");
@@ -30,15 +57,18 @@ fn parse_teams(teams_str: &String) -> Result<Vec<String>, &'static str> {
     Ok(team_names)
 }
 
-fn parse_result(result_str: &String, table: &mut tables::Table) -> Result<(), &'static str> {
+// Parses a result line into a match between two teams of the table without adding it.
+fn parse_match(result_str: &str, table: &tables::Table) -> Result<matches::Match, &'static str> {
     let parts: Vec<&str> = result_str.split(";").collect();
 
-    if parts.len() != 2 {
+    if parts.len() < 2 {
         return Err("Invalid result format.");
     }
 
+    // The score may be followed by a qualifier, e.g. "3-3 (5-4 p)" or "3-0 w.o.".
+    let (score_str, qualifier) = matches::split_score(parts[1]);
     let teams: Vec<&str> = parts[0].split('-').collect();
-    let score: Vec<&str> = parts[1].split('-').collect();
+    let score: Vec<&str> = score_str.split('-').collect();
 
     if teams.len() != 2 || score.len() != 2 {
         return Err("Invalid score format.");
@@ -48,13 +78,13 @@ fn parse_result(result_str: &String, table: &mut tables::Table) -> Result<(), &'
     let away_team = teams[1].trim();
     let home_team_exists = table.teams.iter().any(|team| team.name == home_team);
 
-    if home_team_exists == false {
+    if !home_team_exists {
         return Err("Home team does not exist.");
     }
 
     let away_team_exists = table.teams.iter().any(|team| team.name == away_team);
 
-    if away_team_exists == false {
+    if !away_team_exists {
         return Err("Away team does not exist");
     }
 
@@ -66,30 +96,105 @@ fn parse_result(result_str: &String, table: &mut tables::Table) -> Result<(), &'
         .trim()
         .parse::<i32>()
         .map_err(|_| "Cannot parse the away team score.")?;
+    let result_type = matches::parse_result_type(qualifier)?;
+    if matches!(result_type, matches::ResultType::Penalties(..)) && goal_home_team != goal_away_team
+    {
+        return Err("A shootout needs a level score.");
+    }
+
+    // Optional fields after the score: the round, the kick-off in Swedish local time, the
+    // goals and/or the cards, e.g. "AIK-BP;1-0;3;2024-04-14 15:00;Berg 34';cards:Ali 12' (y)"
+    // (see `scorers::parse_goals` and `cards::parse_cards`).
+    let mut round: Option<u32> = None;
+    let mut kick_off = None;
+    let mut goals = Vec::new();
+    let mut match_cards = Vec::new();
+    for field in parts[2..].iter().map(|field| field.trim()) {
+        if field.is_empty() {
+            continue;
+        }
+        if field.starts_with("cards:") {
+            match_cards = cards::parse_cards(field)?;
+        } else if field.contains('\'') {
+            goals = scorers::parse_goals(field, goal_home_team, goal_away_team)?;
+        } else if let Ok(parsed_round) = field.parse::<u32>() {
+            round = Some(parsed_round);
+        } else if let Ok(parsed_kick_off) = calendar::parse_kick_off(field) {
+            kick_off = Some(parsed_kick_off);
+        } else {
+            return Err("Cannot parse the round or kick-off.");
+        }
+    }
+    let round = round.unwrap_or_else(|| table.next_round(home_team, away_team));
+
+    Ok(matches::Match {
+        kick_off,
+        result_type,
+        goals,
+        cards: match_cards,
+        ..matches::create_match(home_team, away_team, goal_home_team, goal_away_team, round)
+    })
+}
+
+// Parses a result and adds it to the table once its goal and card events check out against
+// the squads.
+fn parse_result(
+    result_str: &str,
+    table: &mut tables::Table,
+    registered: &squads::Squads,
+) -> Result<(), &'static str> {
+    let mut game = parse_match(result_str, table)?;
+    registered.resolve_events(&mut game)?;
 
     println!(
-        "Adding {} - {} {}-{}",
-        home_team, away_team, goal_home_team, goal_away_team
+        "Adding {} - {} {} (round {})",
+        game.home_team,
+        game.away_team,
+        game.score(),
+        game.round
     );
 
-    table.add_game(home_team, away_team, goal_home_team, goal_away_team);
+    table.add_match(game);
 
-    return Ok(());
+    Ok(())
 }
 
-pub fn add_result(table: &mut tables::Table) {
-    println!("Add result(team1-team2;xx-xx). Input \"done\" when you are finished.");
+// Adds a result to the division both teams play in.
+fn route_result(result_str: &str, league: &mut league::League) -> Result<(), &'static str> {
+    let teams: Vec<&str> = result_str
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .split('-')
+        .collect();
+    if teams.len() != 2 {
+        return Err("Invalid score format.");
+    }
+
+    let index = league.division_for_match(teams[0].trim(), teams[1].trim())?;
+    parse_result(
+        result_str,
+        &mut league.divisions[index].table,
+        &league.squads,
+    )
+}
+
+pub fn add_result(league: &mut league::League) {
+    println!("Add result(team1-team2;xx-xx;round;YYYY-MM-DD HH:MM). Round and kick-off are optional. Input \"done\" when you are finished.");
+    println!("The score may be followed by aet, (x-x p), w.o., abandoned or annulled.");
+    println!("Add goals as a field such as \"Berg 34', Ali 60' (pen)|Nilsson 80' (og)\", home goals first.");
+    println!("Add cards as a field such as \"cards:Berg 55' (y)|Ali 70' (r)\". Players can be given as #<shirt number>.");
 
     let mut result_input = String::new();
     while result_input != "done" {
         result_input.clear();
-        if let Ok(_) = io::stdin().read_line(&mut result_input) {
+        if io::stdin().read_line(&mut result_input).is_ok() {
             result_input.pop();
 
             if result_input == "done" {
                 return;
             }
-            let result = parse_result(&result_input, table);
+            let result = route_result(&result_input, league);
 
             if result.is_err() {
                 println!("{:?}", result);
@@ -98,7 +203,7 @@ pub fn add_result(table: &mut tables::Table) {
     }
 }
 
-pub fn read_result_from_file(table: &mut tables::Table) -> std::io::Result<()> {
+pub fn read_result_from_file(league: &mut league::League) -> std::io::Result<()> {
     println!("Reading result file...");
 
     let read_file = File::open("results.txt")?;
@@ -107,7 +212,7 @@ pub fn read_result_from_file(table: &mut tables::Table) -> std::io::Result<()> {
 
     for line in reader.lines() {
         let line_contents = line?;
-        let result = parse_result(&line_contents, table);
+        let result = route_result(&line_contents, league);
         if result.is_err() {
             println!("{:?}", result);
         }
@@ -117,10 +222,15 @@ pub fn read_result_from_file(table: &mut tables::Table) -> std::io::Result<()> {
 }
 
 pub fn save_table_to_file(table: &tables::Table) -> std::io::Result<()> {
+    save_named_table_to_file(table, "table")
+}
+
+// Saves the table as "<name>_<timestamp>.txt" in the same format as `read_table_from_file`.
+pub fn save_named_table_to_file(table: &tables::Table, name: &str) -> std::io::Result<()> {
     let frm_date_time_now = Local::now().format("%Y-%m-%d-%H-%M-%S").to_string();
-    println!("table_{}", frm_date_time_now);
+    println!("{}_{}", name, frm_date_time_now);
 
-    let filename = format!("table_{}.txt", frm_date_time_now);
+    let filename = format!("{}_{}.txt", name, frm_date_time_now);
 
     let mut file = File::create(filename)?;
 
@@ -145,15 +255,1467 @@ pub fn save_table_to_file(table: &tables::Table) -> std::io::Result<()> {
     Ok(())
 }
 
+// Writes the position history as "position_history_<timestamp>.csv" and a bump chart as
+// "position_history_<timestamp>.svg".
+pub fn save_position_history(table: &tables::Table) -> std::io::Result<()> {
+    let history = history::position_history(table);
+    if history.rounds.is_empty() {
+        println!("No matches have been played yet.");
+        return Ok(());
+    }
+
+    let frm_date_time_now = Local::now().format("%Y-%m-%d-%H-%M-%S").to_string();
+    let csv_filename = format!("position_history_{}.csv", frm_date_time_now);
+    let svg_filename = format!("position_history_{}.svg", frm_date_time_now);
+
+    File::create(&csv_filename)?.write_all(history.to_csv().as_bytes())?;
+    File::create(&svg_filename)?.write_all(history.to_svg().as_bytes())?;
+
+    println!("Saved {} and {}", csv_filename, svg_filename);
+    Ok(())
+}
+
+// Prints the season statistics and optionally saves them as "season_stats_<timestamp>.json".
+pub fn print_season_stats(table: &tables::Table) -> std::io::Result<()> {
+    let season_stats = stats::season_stats(table, stats::DEFAULT_TOP_MATCHES);
+    print!("{}", season_stats.to_text());
+
+    let Some(save_input) = read_input("Save as JSON? (y/N): ") else {
+        return Ok(());
+    };
+    if save_input.to_lowercase() == "y" {
+        let frm_date_time_now = Local::now().format("%Y-%m-%d-%H-%M-%S").to_string();
+        let json_filename = format!("season_stats_{}.json", frm_date_time_now);
+        File::create(&json_filename)?.write_all(season_stats.to_json().as_bytes())?;
+        println!("Saved {}", json_filename);
+    }
+
+    Ok(())
+}
+
+pub fn print_table_after_round(table: &tables::Table) {
+    if !has_match_log(table) {
+        return;
+    }
+
+    let prompt = format!("Input the round (1-{}): ", table.last_round());
+    let Some(round_input) = read_input(&prompt) else {
+        return;
+    };
+
+    match round_input.parse::<u32>() {
+        Ok(round) if (1..=table.last_round()).contains(&round) => table.print_round(round),
+        Ok(_) => println!("The round must be between 1 and {}.", table.last_round()),
+        Err(_) => println!("Cannot parse the round."),
+    }
+}
+
+// Standings by round or date are rebuilt from the match log, which a table read from a table
+// file does not have.
+fn has_match_log(table: &tables::Table) -> bool {
+    if table.matches.is_empty() {
+        println!("There is no match log. Read the results from file to rebuild the standings.");
+        return false;
+    }
+    if !table.has_complete_match_log() {
+        println!("The match log does not hold every game of the table, nothing to rebuild.");
+        return false;
+    }
+    true
+}
+
+pub fn print_venue_table(table: &tables::Table) -> std::io::Result<()> {
+    let Some(venue_input) = read_input("Input the venue (home/away): ") else {
+        return Ok(());
+    };
+
+    let Some(venue) = matches::Venue::from_string(&venue_input) else {
+        println!("Unknown venue.");
+        return Ok(());
+    };
+
+    let mut venue_table = table.venue_table(venue);
+    println!("Table for {} matches", venue.name());
+    venue_table.print();
+    if table.has_unsplit_records() {
+        println!("Records read from a table file are not split by venue and are left out.");
+    }
+
+    if let Some(answer) = read_input("Save this table? (y/n): ") {
+        if answer.eq_ignore_ascii_case("y") {
+            save_named_table_to_file(&venue_table, &format!("table_{}", venue.name()))?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn print_form_table(table: &tables::Table) {
+    let prompt = format!(
+        "Input the number of matches (default {}): ",
+        tables::DEFAULT_FORM_LENGTH
+    );
+    let Some(count_input) = read_input(&prompt) else {
+        return;
+    };
+    let Some(venue_input) = read_input("Input the venue (home/away), or leave empty for all: ")
+    else {
+        return;
+    };
+
+    let count = if count_input.is_empty() {
+        tables::DEFAULT_FORM_LENGTH
+    } else {
+        match count_input.parse::<usize>() {
+            Ok(count) if count > 0 => count,
+            _ => {
+                println!("Cannot parse the number of matches.");
+                return;
+            }
+        }
+    };
+    let venue = if venue_input.is_empty() {
+        None
+    } else {
+        match matches::Venue::from_string(&venue_input) {
+            Some(venue) => Some(venue),
+            None => {
+                println!("Unknown venue.");
+                return;
+            }
+        }
+    };
+
+    let mut form_table = table.form_table(count, venue);
+    let form: Vec<String> = form_table
+        .teams
+        .iter()
+        .map(|team| table.form(&team.name, count, venue))
+        .collect();
+
+    println!("Form table over the last {} matches", count);
+    form_table.print_with_columns(&[("Form", form)]);
+}
+
+fn read_input(prompt: &str) -> Option<String> {
+    println!("{}", prompt);
+
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input).is_err() {
+        println!("Cannot read input data.");
+        return None;
+    }
+    Some(input.trim().to_string())
+}
+
+pub fn print_table_on_date(table: &tables::Table) {
+    if !has_match_log(table) {
+        return;
+    }
+
+    let Some(date_input) = read_input("Input the date (YYYY-MM-DD): ") else {
+        return;
+    };
+
+    match calendar::parse_date(&date_input) {
+        Ok(date) => {
+            println!("Standings on {}", date);
+            table.standings_on(date).print();
+        }
+        Err(error) => println!("{}", error),
+    }
+}
+
+pub fn list_matches_between_dates(table: &tables::Table) {
+    let Some(from_input) = read_input("Input the first date (YYYY-MM-DD): ") else {
+        return;
+    };
+    let Some(to_input) = read_input("Input the last date (YYYY-MM-DD): ") else {
+        return;
+    };
+    let Some(weekday_input) =
+        read_input("Input a weekday to filter on (e.g. sun), or leave empty: ")
+    else {
+        return;
+    };
+
+    let dates = calendar::parse_date(&from_input)
+        .and_then(|from| calendar::parse_date(&to_input).map(|to| (from, to)));
+    let weekday = if weekday_input.is_empty() {
+        Ok(None)
+    } else {
+        calendar::parse_weekday(&weekday_input).map(Some)
+    };
+
+    match (dates, weekday) {
+        (Ok((from, to)), Ok(weekday)) => {
+            let found = table.matches_between(from, to, weekday);
+            if found.is_empty() {
+                println!("No matches found.");
+            }
+            found.iter().for_each(|game| println!("{}", game));
+        }
+        (Err(error), _) | (_, Err(error)) => println!("{}", error),
+    }
+}
+
+pub fn simulate_season(table: &tables::Table) {
+    if !has_schedule(table) {
+        return;
+    }
+
+    let mut config = simulation::SimulationConfig {
+        zones: table.zones.clone(),
+        ..Default::default()
+    };
+
+    let prompt = format!(
+        "Input the number of seasons to simulate (default {}): ",
+        config.iterations
+    );
+    let Some(iterations_input) = read_input(&prompt) else {
+        return;
+    };
+    let prompt = format!("Input the random seed (default {}): ", config.seed);
+    let Some(seed_input) = read_input(&prompt) else {
+        return;
+    };
+
+    if !iterations_input.is_empty() {
+        match iterations_input.parse::<usize>() {
+            Ok(iterations) if iterations > 0 => config.iterations = iterations,
+            _ => {
+                println!("Cannot parse the number of seasons.");
+                return;
+            }
+        }
+    }
+    if !seed_input.is_empty() {
+        match seed_input.parse::<u64>() {
+            Ok(seed) => config.seed = seed,
+            Err(_) => {
+                println!("Cannot parse the seed.");
+                return;
+            }
+        }
+    }
+
+    let Some(model_input) =
+        read_input("Input the match model (dixon-coles/table, default dixon-coles): ")
+    else {
+        return;
+    };
+
+    let mut current = table.clone();
+    current.update_table();
+    let remaining = current
+        .remaining_fixtures()
+        .map_or(0, |remaining| remaining.len());
+    println!(
+        "Simulating {} remaining fixtures (threads: {})...",
+        remaining, config.threads
+    );
+
+    let report = match model_input.to_lowercase().as_str() {
+        "" | "dixon-coles" => {
+            let model = fit_prediction_model(&current);
+            simulation::simulate_season(&current, &model, &config)
+        }
+        "table" => {
+            let model = simulation::TableModel::from_table(&current);
+            simulation::simulate_season(&current, &model, &config)
+        }
+        _ => {
+            println!("Unknown match model.");
+            return;
+        }
+    };
+    match report {
+        Ok(report) => report.print(),
+        Err(error) => println!("{}", error),
+    }
+}
+
+// Prints the table ordered by points per game and a projected final table, either from each
+// team's points per game or from the prediction model.
+pub fn print_projected_table(table: &tables::Table) {
+    projection::print_points_per_game_table(table);
+
+    let Some(method_input) =
+        read_input("Project the final table with (ppg/dixon-coles, default ppg): ")
+    else {
+        return;
+    };
+
+    match method_input.to_lowercase().as_str() {
+        "" | "ppg" => projection::print_projection(&projection::project_by_points_per_game(table)),
+        "dixon-coles" => {
+            if has_schedule(table) {
+                let model = fit_prediction_model(table);
+                match projection::project_with_model(table, &model) {
+                    Ok(projection) => projection::print_projection(&projection),
+                    Err(error) => println!("{}", error),
+                }
+            }
+        }
+        _ => println!("Unknown projection method."),
+    }
+}
+
+// Simulations and model projections play out the remaining fixtures, which are unknown for a
+// table read from a table file, even with a fixtures file, since its log misses the games
+// behind the records.
+fn has_schedule(table: &tables::Table) -> bool {
+    if !table.has_known_schedule() {
+        println!("The remaining fixtures are unknown. Read all results of the season.");
+    }
+    table.has_known_schedule()
+}
+
+fn fit_prediction_model(table: &tables::Table) -> prediction::DixonColesModel {
+    let team_names: Vec<String> = table.teams.iter().map(|team| team.name.clone()).collect();
+    let played: Vec<matches::Match> = table
+        .matches
+        .iter()
+        .filter(|game| game.counts_in_statistics())
+        .cloned()
+        .collect();
+    prediction::DixonColesModel::fit(&team_names, &played, &prediction::FitConfig::default())
+}
+
+// Predicts a fixture given as "team1 vs team2" or "team1-team2".
+pub fn predict_match(table: &tables::Table) {
+    let Some((home_team, away_team)) = read_team_pair("Input the fixture (team1 vs team2): ")
+    else {
+        return;
+    };
+
+    for team_name in [&home_team, &away_team] {
+        if !table.teams.iter().any(|team| &team.name == team_name) {
+            println!("{} does not exist.", team_name);
+            return;
+        }
+    }
+
+    fit_prediction_model(table).print_prediction(&home_team, &away_team);
+}
+
+// Prints every meeting between two teams and the record of the first one. The current season
+// replaces its archived copy, if any, so its meetings are not counted twice.
+pub fn print_head_to_head(archive: &archive::Archive, table: &tables::Table) {
+    let Some((team1, team2)) = read_team_pair("Input the teams (team1 vs team2): ") else {
+        return;
+    };
+
+    let mut every_season = archive.clone();
+    every_season
+        .seasons
+        .retain(|season| !archive::is_season_of(season, table));
+    every_season.seasons.push(current_season(table));
+    every_season.head_to_head(&team1, &team2).print();
+}
+
+// Aggregates the chosen seasons into an all-time table. The loaded table is the season
+// "current".
+pub fn print_all_time_table(archive: &archive::Archive, table: &tables::Table) {
+    let with_current = archive_with_current(archive, table);
+    let ids: Vec<&str> = with_current
+        .seasons
+        .iter()
+        .map(|season| season.id.as_str())
+        .collect();
+    let prompt = format!(
+        "Input the seasons separated by commas ({}), empty for all: ",
+        ids.join(", ")
+    );
+    let Some(seasons_input) = read_input(&prompt) else {
+        return;
+    };
+
+    let chosen_ids: Vec<&str> = seasons_input
+        .split(',')
+        .map(|id| id.trim())
+        .filter(|id| !id.is_empty())
+        .collect();
+    let seasons = match with_current.select(&table.name, &chosen_ids) {
+        Ok(seasons) => seasons,
+        Err(error) => {
+            println!("{}", error);
+            return;
+        }
+    };
+
+    let mut all_time = archive::all_time_table(&seasons);
+    let seasons_played: Vec<String> = archive::seasons_played(&all_time, &seasons)
+        .iter()
+        .map(|count| count.to_string())
+        .collect();
+
+    println!("All-time table");
+    all_time.print_with_columns(&[("Seasons", seasons_played)]);
+}
+
+// Compares two seasons, e.g. "2023 vs current", team by team.
+pub fn print_season_comparison(archive: &archive::Archive, table: &tables::Table) {
+    let Some((first_id, second_id)) = read_team_pair("Input the seasons (2023 vs current): ")
+    else {
+        return;
+    };
+
+    let with_current = archive_with_current(archive, table);
+    match (
+        with_current.season(&first_id, &table.name),
+        with_current.season(&second_id, &table.name),
+    ) {
+        (Some(first), Some(second)) => match comparison::compare_seasons(first, second) {
+            Ok(season_comparison) => season_comparison.print(),
+            Err(error) => println!("{}", error),
+        },
+        _ => println!("Unknown season."),
+    }
+}
+
+// The archived seasons of the table's competition followed by the table itself as "current".
+fn archive_with_current(archive: &archive::Archive, table: &tables::Table) -> archive::Archive {
+    let mut with_current = archive::Archive {
+        seasons: archive
+            .seasons
+            .iter()
+            .filter(|season| season.competition == table.name)
+            .cloned()
+            .collect(),
+    };
+    with_current.seasons.push(current_season(table));
+    with_current
+}
+
+fn current_season(table: &tables::Table) -> archive::Season {
+    let mut current = table.clone();
+    current.update_table();
+    archive::Season {
+        id: String::from("current"),
+        competition: table.name.clone(),
+        table: current,
+    }
+}
+
+// Reads two names given as "name1 vs name2" or "name1-name2".
+fn read_team_pair(prompt: &str) -> Option<(String, String)> {
+    let pair_input = read_input(prompt)?;
+
+    let teams: Vec<&str> = if pair_input.contains(" vs ") {
+        pair_input.split(" vs ").collect()
+    } else {
+        pair_input.split('-').collect()
+    };
+    if teams.len() != 2 {
+        println!("Invalid format, use \"name1 vs name2\".");
+        return None;
+    }
+
+    Some((teams[0].trim().to_string(), teams[1].trim().to_string()))
+}
+
+// Reads the season schedule, one fixture per line (see `fixtures::parse_fixture`).
+pub fn read_fixtures_from_file(file_name: &str) -> std::io::Result<Vec<fixtures::Fixture>> {
+    let read_file = File::open(file_name)?;
+    let mut schedule = Vec::new();
+
+    for line in BufReader::new(read_file).lines() {
+        let line_contents = line?;
+        if line_contents.trim().is_empty() {
+            continue;
+        }
+        match fixtures::parse_fixture(&line_contents) {
+            Ok(fixture) => schedule.push(fixture),
+            Err(error) => println!("{}: {}", line_contents, error),
+        }
+    }
+
+    println!("Read {} fixtures from {}", schedule.len(), file_name);
+    Ok(schedule)
+}
+
+// Lists, postpones and reschedules the fixtures of the table and saves the schedule.
+pub fn fixtures_menu(table: &mut tables::Table) {
+    loop {
+        println!("1. List remaining fixtures.");
+        println!("2. Postpone a fixture.");
+        println!("3. Reschedule a postponed fixture.");
+        println!("4. Save fixtures.");
+        println!("5. Back.");
+
+        let Some(choice) = read_input("") else {
+            return;
+        };
+
+        match choice.as_str() {
+            "1" => print_remaining_fixtures(table),
+            "2" => postpone_fixture(table),
+            "3" => reschedule_fixture(table),
+            "4" => {
+                if let Err(error) = save_fixtures_to_file(table) {
+                    println!("Failed to save the fixtures: {}", error);
+                }
+            }
+            "5" => return,
+            _ => println!("Unknown command."),
+        }
+    }
+}
+
+// Remaining fixtures by kick-off, those without a date last.
+fn print_remaining_fixtures(table: &tables::Table) {
+    let mut remaining = match table.remaining_fixtures() {
+        Ok(remaining) => remaining,
+        Err(error) => {
+            println!("{}", error);
+            return;
+        }
+    };
+    remaining.sort_by_key(|fixture| (fixture.kick_off.is_none(), fixture.kick_off, fixture.round));
+
+    if remaining.is_empty() {
+        println!("No fixtures remaining.");
+    }
+    remaining.iter().for_each(|fixture| println!("{}", fixture));
+}
+
+fn postpone_fixture(table: &mut tables::Table) {
+    let Some((home_team, away_team)) = read_team_pair("Input the fixture (team1 vs team2): ")
+    else {
+        return;
+    };
+    let Some(reason) = read_input("Input the reason: ") else {
+        return;
+    };
+
+    match table.postpone(&home_team, &away_team, &reason) {
+        Ok(()) => println!("{} - {} is postponed.", home_team, away_team),
+        Err(error) => println!("{}", error),
+    }
+}
+
+fn reschedule_fixture(table: &mut tables::Table) {
+    let Some((home_team, away_team)) = read_team_pair("Input the fixture (team1 vs team2): ")
+    else {
+        return;
+    };
+    let Some(kick_off_input) = read_input("Input the new kick-off (YYYY-MM-DD HH:MM): ") else {
+        return;
+    };
+
+    let result = calendar::parse_kick_off(&kick_off_input)
+        .and_then(|kick_off| table.reschedule(&home_team, &away_team, kick_off));
+    match result {
+        Ok(()) => println!("{} - {} is rescheduled.", home_team, away_team),
+        Err(error) => println!("{}", error),
+    }
+}
+
+// Writes the schedule, postponements included, as "fixtures_<timestamp>.txt" in the format
+// of `read_fixtures_from_file`.
+pub fn save_fixtures_to_file(table: &tables::Table) -> std::io::Result<()> {
+    if table.fixtures.is_empty() {
+        println!("No fixture schedule loaded.");
+        return Ok(());
+    }
+
+    let frm_date_time_now = Local::now().format("%Y-%m-%d-%H-%M-%S").to_string();
+    let filename = format!("fixtures_{}.txt", frm_date_time_now);
+
+    let mut file = File::create(&filename)?;
+    for fixture in &table.fixtures {
+        writeln!(file, "{}", fixtures::format_fixture(fixture))?;
+    }
+
+    println!("Saved {}", filename);
+    Ok(())
+}
+
+pub fn print_ratings(table: &tables::Table) {
+    let team_names: Vec<String> = table.teams.iter().map(|team| team.name.clone()).collect();
+    table.ratings.print(&team_names);
+
+    let Some(team_input) = read_input("Input a team to show its rating history, or leave empty: ")
+    else {
+        return;
+    };
+    if !team_input.is_empty() {
+        table.ratings.print_team_history(&team_input);
+    }
+}
+
+// Plays out a knockout tie, e.g. the relegation playoff. The teams may come from any division.
+pub fn play_tie(league: &league::League) {
+    let Some(legs_input) = read_input("Input the number of legs (1/2, default 2): ") else {
+        return;
+    };
+    let Some(away_goals_input) = read_input("Do away goals count? (y/N): ") else {
+        return;
+    };
+    let rules = tie::TieRules {
+        legs: if legs_input == "1" { 1 } else { 2 },
+        away_goals: away_goals_input.to_lowercase() == "y",
+        ..Default::default()
+    };
+
+    let registry = league.registry();
+    let mut current_tie: Option<tie::Tie> = None;
+    for leg in 1..=rules.legs {
+        let prompt = format!("Input leg {} (team1-team2;xx-xx): ", leg);
+        let Some(leg_input) = read_input(&prompt) else {
+            return;
+        };
+
+        let parsed = parse_match(&leg_input, &registry).map(|game| matches::Match {
+            round: leg as u32,
+            ..game
+        });
+        let added = parsed.and_then(|game| match &mut current_tie {
+            Some(existing) => existing.add_leg(game),
+            None => {
+                current_tie = Some(tie::create_tie(game));
+                Ok(())
+            }
+        });
+        if let Err(error) = added {
+            println!("{}", error);
+            return;
+        }
+    }
+    let Some(mut current_tie) = current_tie else {
+        return;
+    };
+
+    if read_tie_deciders(&mut current_tie, &rules).is_none() {
+        return;
+    }
+
+    current_tie.print(&rules);
+}
+
+// Asks for the extra time and penalty scores while the tie is level and the rules allow them.
+// Returns `None` when the input ended or could not be parsed.
+fn read_tie_deciders(current_tie: &mut tie::Tie, rules: &tie::TieRules) -> Option<()> {
+    // A result such as "2-2 aet" or "1-1 (5-4 p)" already includes the goals of extra time,
+    // see `Tie::went_to_extra_time`.
+    let last_result_type = current_tie.legs.last().map(|leg| leg.result_type);
+    if let Some(matches::ResultType::Penalties(home_penalties, away_penalties)) = last_result_type {
+        current_tie.penalties = Some((home_penalties, away_penalties));
+    }
+
+    let deciders = [
+        ("extra time", rules.extra_time, false),
+        ("penalties", rules.penalties, true),
+    ];
+    for (name, _, is_penalties) in deciders.iter().filter(|(_, used, _)| *used) {
+        if current_tie.outcome(rules) != tie::TieOutcome::Undecided {
+            break;
+        }
+        if !is_penalties && current_tie.went_to_extra_time() {
+            continue;
+        }
+        let prompt = format!("Input the {} score (xx-xx): ", name);
+        let score_input = read_input(&prompt)?;
+        match tie::parse_score(&score_input) {
+            Ok(score) if *is_penalties => current_tie.penalties = Some(score),
+            Ok(score) => current_tie.extra_time = Some(score),
+            Err(error) => {
+                println!("{}", error);
+                return None;
+            }
+        }
+    }
+
+    Some(())
+}
+
+// Runs a knockout cup: create it from a list of teams, draw every round, enter the results
+// and show the bracket. Every team must be in `registry`, e.g. the teams of every division
+// of the league.
+pub fn cup_menu(registry: &tables::Table, current_cup: &mut Option<cup::Cup>) {
+    loop {
+        println!(
+            "Cup: {}",
+            current_cup
+                .as_ref()
+                .map_or("none", |existing| &existing.name)
+        );
+        println!("1. New cup.");
+        println!("2. Draw the next round.");
+        println!("3. Add cup result.");
+        println!("4. Print bracket.");
+        println!("5. Save bracket as SVG.");
+        println!("6. Back.");
+
+        let Some(choice) = read_input("") else {
+            return;
+        };
+
+        match (choice.as_str(), current_cup.as_mut()) {
+            ("1", _) => {
+                if let Some(created) = create_cup_from_input(registry) {
+                    *current_cup = Some(created);
+                }
+            }
+            ("2", Some(existing)) => draw_cup_round(existing),
+            ("3", Some(existing)) => add_cup_result(registry, existing),
+            ("4", Some(existing)) => existing.print(),
+            ("5", Some(existing)) => {
+                if let Err(error) = save_cup_bracket(existing) {
+                    println!("Failed to save the bracket: {}", error);
+                }
+            }
+            ("2" | "3" | "4" | "5", None) => println!("Create a cup first."),
+            ("6", _) => return,
+            _ => println!("Unknown command."),
+        }
+    }
+}
+
+fn create_cup_from_input(registry: &tables::Table) -> Option<cup::Cup> {
+    let name = read_input("Input the cup name: ")?;
+    let teams_input = read_input("Input the teams separated by commas, best seed first: ")?;
+
+    match parse_teams(&teams_input) {
+        Ok(teams) if teams.len() >= 2 => {
+            if let Some(unknown) = teams.iter().find(|team| registry.position(team).is_none()) {
+                println!("{} does not exist.", unknown);
+                return None;
+            }
+            Some(cup::create_cup(&name, &teams))
+        }
+        Ok(_) => {
+            println!("A cup needs at least two teams.");
+            None
+        }
+        Err(error) => {
+            println!("{}", error);
+            None
+        }
+    }
+}
+
+fn draw_cup_round(existing: &mut cup::Cup) {
+    let Some(mode_input) = read_input("Seeded or random draw? (seeded/random, default random): ")
+    else {
+        return;
+    };
+
+    let mode = if mode_input.to_lowercase() == "seeded" {
+        cup::DrawMode::Seeded
+    } else {
+        let Some(seed_input) = read_input("Input the random seed (default 0): ") else {
+            return;
+        };
+        match seed_input.parse::<u64>() {
+            Ok(seed) => cup::DrawMode::Random(seed),
+            Err(_) if seed_input.is_empty() => cup::DrawMode::Random(0),
+            Err(_) => {
+                println!("Cannot parse the seed.");
+                return;
+            }
+        }
+    };
+
+    match existing.draw(mode) {
+        Ok(()) => existing.print(),
+        Err(error) => println!("{}", error),
+    }
+}
+
+// Reads a cup result in the same format as league results.
+fn add_cup_result(registry: &tables::Table, existing: &mut cup::Cup) {
+    let Some(result_input) = read_input("Input the result (team1-team2;xx-xx): ") else {
+        return;
+    };
+
+    let mut cup_tie = match parse_match(&result_input, registry) {
+        Ok(game) => tie::create_tie(matches::Match {
+            round: existing.rounds.len() as u32,
+            ..game
+        }),
+        Err(error) => {
+            println!("{}", error);
+            return;
+        }
+    };
+    if read_tie_deciders(&mut cup_tie, &existing.rules).is_none() {
+        return;
+    }
+
+    if let Err(error) = existing.add_result(cup_tie) {
+        println!("{}", error);
+    }
+}
+
+// Writes the bracket as "<cup name>_<timestamp>.svg".
+fn save_cup_bracket(existing: &cup::Cup) -> std::io::Result<()> {
+    let frm_date_time_now = Local::now().format("%Y-%m-%d-%H-%M-%S").to_string();
+    let svg_filename = format!(
+        "{}_{}.svg",
+        existing.name.to_lowercase().replace(' ', "_"),
+        frm_date_time_now
+    );
+
+    File::create(&svg_filename)?.write_all(existing.to_svg().as_bytes())?;
+
+    println!("Saved {}", svg_filename);
+    Ok(())
+}
+
+pub fn tournament_menu(current_tournament: &mut Option<tournament::Tournament>) {
+    loop {
+        println!(
+            "Tournament: {}",
+            current_tournament
+                .as_ref()
+                .map_or("none", |existing| &existing.name)
+        );
+        println!("1. New tournament.");
+        println!("2. Add group result.");
+        println!("3. Print groups.");
+        println!("4. Start the knockout stage.");
+        println!("5. Knockout stage.");
+        println!("6. Back.");
+
+        let Some(choice) = read_input("") else {
+            return;
+        };
+
+        match (choice.as_str(), current_tournament.as_mut()) {
+            ("1", _) => {
+                if let Some(created) = create_tournament_from_input() {
+                    *current_tournament = Some(created);
+                }
+            }
+            ("2", Some(existing)) => add_group_result(existing),
+            ("3", Some(existing)) => existing.print_groups(),
+            ("4", Some(existing)) => match existing.start_knockout() {
+                Ok(()) => existing
+                    .knockout
+                    .iter()
+                    .for_each(|knockout| knockout.print()),
+                Err(error) => println!("{}", error),
+            },
+            ("5", Some(existing)) => match existing.knockout.as_mut() {
+                Some(knockout) => knockout_menu(knockout),
+                None => println!("Start the knockout stage first."),
+            },
+            ("2" | "3" | "4" | "5", None) => println!("Create a tournament first."),
+            ("6", _) => return,
+            _ => println!("Unknown command."),
+        }
+    }
+}
+
+// The knockout stage of a tournament. It was seeded from the groups, so unlike `cup_menu` it
+// cannot be replaced by a new cup.
+fn knockout_menu(knockout: &mut cup::Cup) {
+    let registry = tables::create_table(&knockout.entrants);
+    loop {
+        println!("Knockout stage: {}", knockout.name);
+        println!("1. Draw the next round.");
+        println!("2. Add knockout result.");
+        println!("3. Print bracket.");
+        println!("4. Save bracket as SVG.");
+        println!("5. Back.");
+
+        let Some(choice) = read_input("") else {
+            return;
+        };
+
+        match choice.as_str() {
+            "1" => draw_cup_round(knockout),
+            "2" => add_cup_result(&registry, knockout),
+            "3" => knockout.print(),
+            "4" => {
+                if let Err(error) = save_cup_bracket(knockout) {
+                    println!("Failed to save the bracket: {}", error);
+                }
+            }
+            "5" => return,
+            _ => println!("Unknown command."),
+        }
+    }
+}
+
+fn create_tournament_from_input() -> Option<tournament::Tournament> {
+    let name = read_input("Input the tournament name: ")?;
+    let group_count = read_count("Input the number of groups: ")?;
+
+    let mut groups = Vec::new();
+    for index in 0..group_count {
+        let prompt = format!(
+            "Input the teams of group {} separated by commas: ",
+            (b'A' + index as u8) as char
+        );
+        match parse_teams(&read_input(&prompt)?) {
+            Ok(teams) if teams.len() >= 2 => groups.push(teams),
+            Ok(_) => {
+                println!("A group needs at least two teams.");
+                return None;
+            }
+            Err(error) => {
+                println!("{}", error);
+                return None;
+            }
+        }
+    }
+
+    let qualifiers_per_group = read_count("Input the number of teams per group that go through: ")?;
+    let best_next_placed = read_count(&format!(
+        "Input the number of best teams in position {} that also go through: ",
+        qualifiers_per_group + 1
+    ))?;
+
+    Some(tournament::create_tournament(
+        &name,
+        &groups,
+        qualifiers_per_group,
+        best_next_placed,
+    ))
+}
+
+fn read_count(prompt: &str) -> Option<usize> {
+    let input = read_input(prompt)?;
+    match input.parse::<usize>() {
+        Ok(count) => Some(count),
+        Err(_) => {
+            println!("Cannot parse the number.");
+            None
+        }
+    }
+}
+
+// Reads a group result in the same format as league results. All group teams and matches
+// form the registry, so the round defaults to the next one of the two teams.
+fn add_group_result(existing: &mut tournament::Tournament) {
+    let Some(result_input) = read_input("Input the result (team1-team2;xx-xx): ") else {
+        return;
+    };
+
+    let registry = tables::Table {
+        teams: existing
+            .groups
+            .iter()
+            .flat_map(|group| group.table.teams.iter().cloned())
+            .collect(),
+        matches: existing
+            .groups
+            .iter()
+            .flat_map(|group| group.table.matches.iter().cloned())
+            .collect(),
+        ..Default::default()
+    };
+    let result = parse_match(&result_input, &registry).and_then(|game| existing.add_result(game));
+    if let Err(error) = result {
+        println!("{}", error);
+    }
+}
+
+pub fn print_top_scorers(table: &tables::Table) {
+    scorers::print_top_scorers(&table.name, &scorers::top_scorers(&table.matches));
+}
+
+// Reads the squads, one player registration per line (see `squads::parse_player`).
+pub fn read_squads_from_file(file_name: &str) -> std::io::Result<squads::Squads> {
+    let read_file = File::open(file_name)?;
+    let mut registered = squads::Squads::default();
+
+    for line in BufReader::new(read_file).lines() {
+        let line_contents = line?;
+        if line_contents.trim().is_empty() {
+            continue;
+        }
+        match squads::parse_player(&line_contents) {
+            Ok(player) => registered.add_player(player),
+            Err(error) => println!("{}: {}", line_contents, error),
+        }
+    }
+
+    println!(
+        "Read {} players from {}",
+        registered.players.len(),
+        file_name
+    );
+    Ok(registered)
+}
+
+// Prints a team's squad on a date with every player's goals and cards in the selected
+// division.
+pub fn print_squad(league: &league::League) {
+    let Some(team_name) = read_input("Input the team: ") else {
+        return;
+    };
+    if !league.squads.has_squad(&team_name) {
+        println!("No squad registered for {}.", team_name);
+        return;
+    }
+    let Some(date_input) = read_input("Input the date (YYYY-MM-DD), or leave empty for all: ")
+    else {
+        return;
+    };
+    let date = if date_input.is_empty() {
+        None
+    } else {
+        match calendar::parse_date(&date_input) {
+            Ok(date) => Some(date),
+            Err(error) => {
+                println!("{}", error);
+                return;
+            }
+        }
+    };
+
+    let played: Vec<&matches::Match> = league
+        .table()
+        .matches
+        .iter()
+        .filter(|game| game.counts_in_statistics())
+        .collect();
+    let count_events = |player: &squads::Player| {
+        let goals = played
+            .iter()
+            .flat_map(|game| game.goals.iter().map(move |goal| (game, goal)))
+            .filter(|(game, goal)| {
+                goal.scorer == player.name
+                    && goal.scorer_team(game) == player.team
+                    && goal.goal_type != scorers::GoalType::OwnGoal
+            })
+            .count();
+        let cards_of = |card_types: &[cards::CardType]| {
+            played
+                .iter()
+                .flat_map(|game| game.cards.iter().map(move |card| (game, card)))
+                .filter(|(game, card)| {
+                    card.player == player.name
+                        && card.team(game) == player.team
+                        && card_types.contains(&card.card_type)
+                })
+                .count()
+        };
+        (
+            goals,
+            cards_of(&[cards::CardType::Yellow]),
+            cards_of(&[cards::CardType::SecondYellow, cards::CardType::Red]),
+        )
+    };
+
+    println!("--------- {} ------------", team_name);
+    println!(
+        "{:>3}  {:<2}  {:<24}  {:>3}  {:>5}  {:>2}  {:>2}",
+        "No", "", "Player", "Age", "Goals", "Y", "R"
+    );
+    let age_date = date.unwrap_or_else(|| Local::now().date_naive());
+    for player in league.squads.squad(&team_name, date) {
+        let (goals, yellow_cards, red_cards) = count_events(player);
+        println!(
+            "{:>3}  {:<2}  {:<24}  {:>3}  {:>5}  {:>2}  {:>2}",
+            player.shirt_number,
+            player.position.code(),
+            player.name,
+            player.age_on(age_date),
+            goals,
+            yellow_cards,
+            red_cards
+        );
+    }
+}
+
+// Prints the sections of the selected division once it has split.
+pub fn print_split_tables(league: &league::League) {
+    let Some(rules) = &league.division().competition.split else {
+        println!("{} does not split.", league.division().competition.name);
+        return;
+    };
+
+    match rules.split(league.table()) {
+        Ok(sections) => sections.into_iter().for_each(|mut section| section.print()),
+        Err(error) => println!("{}", error),
+    }
+}
+
+// Loads every competition of the config that has a table file as a division. Without any, the
+// league is Allsvenskan read from `default_table_file`.
+pub fn read_league(
+    competitions: Vec<competition::Competition>,
+    default_table_file: &str,
+) -> league::League {
+    let mut loaded = league::League::default();
+
+    for division in &competitions {
+        if let Some(table_file) = &division.table_file {
+            let table = read_table_from_file(table_file);
+            if !table.teams.is_empty() {
+                loaded.add_division(division.clone(), table);
+            }
+        }
+    }
+
+    if loaded.divisions.is_empty() {
+        let allsvenskan = competitions
+            .into_iter()
+            .find(|division| division.name == "Allsvenskan")
+            .unwrap_or(competition::Competition {
+                name: String::from("Allsvenskan"),
+                zones: zones::Zones::default(),
+                lower_division: None,
+                table_file: None,
+                split: None,
+            });
+        loaded.add_division(allsvenskan, read_table_from_file(default_table_file));
+    }
+
+    loaded
+}
+
+pub fn select_division(league: &mut league::League) {
+    let prompt = format!("Input the division ({}): ", league.names().join(", "));
+    let Some(name) = read_input(&prompt) else {
+        return;
+    };
+
+    match league.select(&name) {
+        Ok(()) => println!("Selected {}.", league.division().competition.name),
+        Err(error) => println!("{}", error),
+    }
+}
+
+// Reads the competition config, one line per competition (see
+// `competition::parse_competition`).
+pub fn read_competitions_from_file(
+    file_name: &str,
+) -> std::io::Result<Vec<competition::Competition>> {
+    let read_file = File::open(file_name)?;
+    let mut competitions = Vec::new();
+
+    for line in BufReader::new(read_file).lines() {
+        let line_contents = line?;
+        if line_contents.trim().is_empty() {
+            continue;
+        }
+        match competition::parse_competition(&line_contents) {
+            Ok(parsed) => competitions.push(parsed),
+            Err(error) => println!("{}: {}", line_contents, error),
+        }
+    }
+
+    Ok(competitions)
+}
+
+// Builds next season's team lists of the competition and its lower division from the final
+// tables and the playoff results, and saves them as empty tables.
+pub fn season_rollover(league: &league::League) {
+    let current = &league.division().competition;
+    let Some(lower_name) = &current.lower_division else {
+        println!("{} has no lower division.", current.name);
+        return;
+    };
+
+    let mut upper = league.table().clone();
+    upper.update_table();
+    let mut lower = match league.find(lower_name) {
+        Some(index) => league.divisions[index].table.clone(),
+        None => {
+            let prompt = format!("Input the {} table file: ", lower_name);
+            let Some(lower_file) = read_input(&prompt) else {
+                return;
+            };
+            read_table_from_file(&lower_file)
+        }
+    };
+    lower.update_table();
+    if lower.teams.is_empty() {
+        return;
+    }
+
+    let mut playoff_winners = Vec::new();
+    for (upper_team, lower_team) in current.playoff_pairs(&upper, &lower) {
+        let prompt = format!("Who won the playoff {} vs {}? ", upper_team, lower_team);
+        let Some(winner) = read_input(&prompt) else {
+            return;
+        };
+        playoff_winners.push(winner);
+    }
+
+    let rollover = match current.rollover(&upper, &lower, &playoff_winners) {
+        Ok(rollover) => rollover,
+        Err(error) => {
+            println!("{}", error);
+            return;
+        }
+    };
+    rollover.print(&current.name, lower_name);
+
+    let Some(save_input) = read_input("Save next season's tables? (y/N): ") else {
+        return;
+    };
+    if save_input.to_lowercase() == "y" {
+        for (name, teams) in [
+            (&current.name, &rollover.upper_teams),
+            (lower_name, &rollover.lower_teams),
+        ] {
+            let file_name = format!("{}_next", name.to_lowercase());
+            if let Err(error) = save_named_table_to_file(&tables::create_table(teams), &file_name) {
+                println!("Failed to save {}: {}", file_name, error);
+            }
+        }
+    }
+}
+
+// Reads the season archive index, one "season id;competition;table file;results file" line
+// per season. The results are added on top of the table, like option 3 does for the current
+// table. A season whose table or results cannot be read is skipped and reported.
+pub fn read_archive_from_file(file_name: &str) -> std::io::Result<archive::Archive> {
+    let read_file = File::open(file_name)?;
+    let mut season_archive = archive::Archive::default();
+
+    for line in BufReader::new(read_file).lines() {
+        let line_contents = line?;
+        if line_contents.trim().is_empty() {
+            continue;
+        }
+
+        let (id, competition, table_file, results_file) =
+            match archive::parse_archive_entry(&line_contents) {
+                Ok(entry) => entry,
+                Err(error) => {
+                    println!("{}: {}", line_contents, error);
+                    continue;
+                }
+            };
+
+        let mut table = read_table_from_file(&table_file);
+        if table.teams.is_empty() {
+            println!("{}: Skipped, the table could not be read.", line_contents);
+            continue;
+        }
+        if let Some(results_file) = results_file {
+            if let Err(error) = read_archived_results(&results_file, &mut table) {
+                println!("{}: Skipped, {}: {}", line_contents, results_file, error);
+                continue;
+            }
+        }
+        table.update_table();
+
+        let season = archive::Season {
+            id,
+            competition,
+            table,
+        };
+        if let Err(error) = season_archive.add_season(season) {
+            println!("{}: {}", line_contents, error);
+        }
+    }
+
+    Ok(season_archive)
+}
+
+fn read_archived_results(results_file: &str, table: &mut tables::Table) -> std::io::Result<()> {
+    for result_line in BufReader::new(File::open(results_file)?).lines() {
+        let result_contents = result_line?;
+        match parse_match(&result_contents, table) {
+            Ok(game) => table.add_match(game),
+            Err(error) => println!("{}: {}", result_contents, error),
+        }
+    }
+    Ok(())
+}
+
+// Reads the rating history written by `save_ratings_to_file`. The current ratings are the
+// latest entry of every team.
+pub fn read_ratings_from_file(file_name: &str) -> std::io::Result<elo::Ratings> {
+    let read_file = File::open(file_name)?;
+    let mut ratings = elo::Ratings::default();
+
+    for line in BufReader::new(read_file).lines() {
+        let line_contents = line?;
+        match elo::parse_rating_change(&line_contents) {
+            Ok(change) => ratings.record(change),
+            Err(error) => println!("{}: {}", line_contents, error),
+        }
+    }
+
+    Ok(ratings)
+}
+
+pub fn save_ratings_to_file(ratings: &elo::Ratings, file_name: &str) -> std::io::Result<()> {
+    if ratings.history.is_empty() {
+        return Ok(());
+    }
+
+    let mut file = File::create(file_name)?;
+
+    for change in &ratings.history {
+        file.write_all(format!("{}\n", elo::format_rating_change(change)).as_bytes())?;
+    }
+
+    println!(
+        "Saved {} rating changes to {}",
+        ratings.history.len(),
+        file_name
+    );
+    Ok(())
+}
+
+// Sandbox for hypothetical results. Scenarios live in `scenarios` until the program exits and
+// never change the real table.
+pub fn scenario_menu(table: &tables::Table, scenarios: &mut Vec<scenario::Scenario>) {
+    if !has_schedule(table) {
+        return;
+    }
+
+    loop {
+        println!("What-if scenarios: {}", scenario_names(scenarios));
+        println!("1. New scenario.");
+        println!("2. Add results to a scenario.");
+        println!("3. Show a scenario.");
+        println!("4. Compare scenarios side by side.");
+        println!("5. Save a scenario to file.");
+        println!("6. Load a scenario from file.");
+        println!("7. Discard a scenario.");
+        println!("8. Back.");
+
+        let Some(choice) = read_input("") else {
+            return;
+        };
+
+        match choice.as_str() {
+            "1" => {
+                if let Some(name) = read_input("Input the scenario name: ") {
+                    if name.is_empty() || scenarios.iter().any(|existing| existing.name == name) {
+                        println!("The name must be new and not empty.");
+                    } else {
+                        let mut new_scenario = scenario::create_scenario(&name);
+                        add_scenario_results(table, &mut new_scenario);
+                        scenarios.push(new_scenario);
+                    }
+                }
+            }
+            "2" => {
+                if let Some(index) = choose_scenario(scenarios) {
+                    add_scenario_results(table, &mut scenarios[index]);
+                }
+            }
+            "3" => {
+                if let Some(index) = choose_scenario(scenarios) {
+                    scenarios[index].print(table);
+                }
+            }
+            "4" => scenario::print_comparison(table, scenarios),
+            "5" => {
+                if let Some(index) = choose_scenario(scenarios) {
+                    if let Err(error) = save_scenario_to_file(&scenarios[index]) {
+                        println!("Failed to save the scenario: {}", error);
+                    }
+                }
+            }
+            "6" => {
+                if let Some(file_name) = read_input("Input the scenario file name: ") {
+                    match read_scenario_from_file(table, &file_name) {
+                        Ok(loaded) => scenarios.push(loaded),
+                        Err(error) => println!("Failed to read the scenario: {}", error),
+                    }
+                }
+            }
+            "7" => {
+                if let Some(index) = choose_scenario(scenarios) {
+                    let discarded = scenarios.remove(index);
+                    println!("Discarded \"{}\".", discarded.name);
+                }
+            }
+            "8" => return,
+            _ => println!("Unknown command."),
+        }
+    }
+}
+
+fn scenario_names(scenarios: &[scenario::Scenario]) -> String {
+    if scenarios.is_empty() {
+        return String::from("none");
+    }
+    scenarios
+        .iter()
+        .map(|existing| existing.name.as_str())
+        .collect::<Vec<&str>>()
+        .join(", ")
+}
+
+fn choose_scenario(scenarios: &[scenario::Scenario]) -> Option<usize> {
+    let name = read_input("Input the scenario name: ")?;
+    let index = scenarios.iter().position(|existing| existing.name == name);
+    if index.is_none() {
+        println!("No scenario named \"{}\".", name);
+    }
+    index
+}
+
+fn add_scenario_results(table: &tables::Table, current: &mut scenario::Scenario) {
+    println!("Add hypothetical results(team1-team2;xx-xx). Input \"done\" when you are finished.");
+
+    while let Some(result_input) = read_input("") {
+        if result_input == "done" {
+            return;
+        }
+
+        let scenario_table = current.apply(table);
+        let added = parse_match(&result_input, &scenario_table)
+            .and_then(|game| current.add_result(table, game));
+        if let Err(error) = added {
+            println!("{}", error);
+        }
+    }
+}
+
+// Writes the scenario's results as "scenario_<name>.txt" in the results file format.
+fn save_scenario_to_file(current: &scenario::Scenario) -> std::io::Result<()> {
+    let file_name = format!("scenario_{}.txt", current.name.replace(' ', "_"));
+    let mut file = File::create(&file_name)?;
+
+    for game in &current.results {
+        file.write_all(format!("{}\n", matches::format_result(game)).as_bytes())?;
+    }
+
+    println!("Saved {}", file_name);
+    Ok(())
+}
+
+fn read_scenario_from_file(
+    table: &tables::Table,
+    file_name: &str,
+) -> std::io::Result<scenario::Scenario> {
+    let read_file = File::open(file_name)?;
+    let name = file_name
+        .trim_start_matches("scenario_")
+        .trim_end_matches(".txt")
+        .replace('_', " ");
+    let mut loaded = scenario::create_scenario(&name);
+
+    for line in BufReader::new(read_file).lines() {
+        let line_contents = line?;
+        let scenario_table = loaded.apply(table);
+        let added = parse_match(&line_contents, &scenario_table)
+            .and_then(|game| loaded.add_result(table, game));
+        if let Err(error) = added {
+            println!("{}: {}", line_contents, error);
+        }
+    }
+
+    println!("Loaded scenario \"{}\"", loaded.name);
+    Ok(loaded)
+}
+
 pub fn read_table_from_input_file() -> tables::Table {
     let mut file_name_input = String::new();
     println!("Input the table name: ");
 
     if std::io::stdin().read_line(&mut file_name_input).is_err() {
         println!("Cannot read input data.");
-        return tables::Table { teams: Vec::new() };
+        return tables::Table::default();
     }
-    return read_table_from_file(&file_name_input);
+    read_table_from_file(file_name_input.trim())
 }
 
 pub fn read_table_from_file(table_name: &str) -> tables::Table {
@@ -162,22 +1724,19 @@ pub fn read_table_from_file(table_name: &str) -> tables::Table {
 
     match File::open(&current_dir) {
         Ok(read_file) => {
-            let teams_and_errors: (
-                Vec<Result<team::TeamStats, &'static str>>,
-                Vec<Result<team::TeamStats, &'static str>>,
-            ) = io::BufReader::new(read_file)
+            let teams_and_errors: (ParsedTeams, ParsedTeams) = io::BufReader::new(read_file)
                 .lines()
-                .filter_map(|line| line.ok())
+                .map_while(Result::ok)
                 .map(|line| team::create_team(&line))
                 .partition(|created_teams| created_teams.is_ok());
 
-            if teams_and_errors.1.len() > 0 {
+            if !teams_and_errors.1.is_empty() {
                 println!("Failed to parse {:?}", &current_dir);
                 teams_and_errors
                     .1
                     .into_iter()
                     .for_each(|f| println!("{}", f.unwrap_err()));
-                return tables::Table { teams: Vec::new() };
+                return tables::Table::default();
             }
 
             let teams: Vec<team::TeamStats> = teams_and_errors
@@ -187,11 +1746,29 @@ pub fn read_table_from_file(table_name: &str) -> tables::Table {
                 .collect();
 
             println!("Table read successfully!");
-            tables::Table { teams: teams }
+            tables::Table {
+                teams,
+                ..Default::default()
+            }
         }
         Err(_) => {
             println!("Couldn't parse table file with name {}", table_name);
-            tables::Table { teams: Vec::new() }
+            tables::Table::default()
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_match_spaces_around_dash() {
+        let table = tables::create_table(&[String::from("AIK"), String::from("BP")]);
+        let game = parse_match("AIK-BP;1 - 0", &table).unwrap();
+
+        assert_eq!(game.home_score, 1);
+        assert_eq!(game.away_score, 0);
+        assert_eq!(game.result_type, matches::ResultType::Normal);
+    }
+}
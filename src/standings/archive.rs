@@ -0,0 +1,298 @@
+use crate::standings::head_to_head;
+use crate::standings::head_to_head::HeadToHead;
+use crate::standings::matches::Match;
+use crate::standings::tables;
+use crate::standings::tables::Table;
+use crate::standings::team::TeamStats;
+
+// One finished or ongoing season of a competition.
+#[derive(Clone, Debug)]
+pub struct Season {
+    pub id: String,
+    pub competition: String,
+    pub table: Table,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Archive {
+    // Oldest season first. Seasons of different competitions, e.g. the divisions of a league,
+    // may share an id.
+    pub seasons: Vec<Season>,
+}
+
+impl Archive {
+    // Adds the season in order of its id, after the archived seasons with the same id.
+    pub fn add_season(&mut self, season: Season) -> Result<(), &'static str> {
+        if self.season(&season.id, &season.competition).is_some() {
+            return Err("The season of the competition is already archived.");
+        }
+
+        let index = self
+            .seasons
+            .partition_point(|archived| archived.id <= season.id);
+        self.seasons.insert(index, season);
+        Ok(())
+    }
+
+    pub fn season(&self, id: &str, competition: &str) -> Option<&Season> {
+        self.seasons
+            .iter()
+            .find(|season| season.id == id && season.competition == competition)
+    }
+
+    // The seasons of the competition with the given ids, in archive order. An empty list
+    // selects every season of the competition.
+    pub fn select(&self, competition: &str, ids: &[&str]) -> Result<Vec<&Season>, &'static str> {
+        if ids.iter().any(|id| self.season(id, competition).is_none()) {
+            return Err("Unknown season.");
+        }
+
+        Ok(self
+            .seasons
+            .iter()
+            .filter(|season| season.competition == competition)
+            .filter(|season| ids.is_empty() || ids.contains(&season.id.as_str()))
+            .collect())
+    }
+
+    // Every meeting of the two teams over all seasons, whatever competition they met in. The
+    // seasons are labelled with their id and competition.
+    pub fn head_to_head(&self, team1: &str, team2: &str) -> HeadToHead {
+        let labels: Vec<String> = self
+            .seasons
+            .iter()
+            .map(|season| format!("{} {}", season.id, season.competition))
+            .collect();
+        let seasons: Vec<(&str, &[Match])> = self
+            .seasons
+            .iter()
+            .zip(&labels)
+            .map(|(season, label)| (label.as_str(), season.table.matches.as_slice()))
+            .collect();
+
+        head_to_head::head_to_head(team1, team2, &seasons)
+    }
+}
+
+// An archive index line: "season id;competition;table file;results file". The results file
+// is optional.
+pub fn parse_archive_entry(
+    entry_str: &str,
+) -> Result<(String, String, String, Option<String>), &'static str> {
+    let parts: Vec<&str> = entry_str.split(';').map(|part| part.trim()).collect();
+
+    if parts.len() < 3 || parts.len() > 4 {
+        return Err("Archive line does not contain three or four parts.");
+    }
+    if parts[..3].iter().any(|part| part.is_empty()) {
+        return Err("Archive line is missing the season, competition or table file.");
+    }
+
+    let results_file = parts
+        .get(3)
+        .filter(|results_file| !results_file.is_empty())
+        .map(|results_file| results_file.to_string());
+
+    Ok((
+        parts[0].to_string(),
+        parts[1].to_string(),
+        parts[2].to_string(),
+        results_file,
+    ))
+}
+
+// Adds up the records of every team over the given seasons. Teams only have to be present in
+// some of the seasons.
+pub fn all_time_table(seasons: &[&Season]) -> Table {
+    let mut teams: Vec<TeamStats> = Vec::new();
+
+    for season in seasons {
+        for season_team in &season.table.teams {
+            let index = match teams.iter().position(|team| team.name == season_team.name) {
+                Some(index) => index,
+                None => {
+                    teams.push(TeamStats {
+                        name: season_team.name.clone(),
+                        ..Default::default()
+                    });
+                    teams.len() - 1
+                }
+            };
+
+            let team = &mut teams[index];
+            team.wins += season_team.wins;
+            team.draws += season_team.draws;
+            team.defeats += season_team.defeats;
+            team.goal_scored += season_team.goal_scored;
+            team.goal_against += season_team.goal_against;
+        }
+    }
+
+    let mut table = Table {
+        name: String::from("All-time"),
+        teams,
+        ..Default::default()
+    };
+    table.update_table();
+    table
+}
+
+// Whether the season is an archived copy of the table's season: the same competition with
+// a match log the table still holds, e.g. the ongoing season archived earlier on.
+pub fn is_season_of(season: &Season, table: &Table) -> bool {
+    season.competition == table.name
+        && !season.table.matches.is_empty()
+        && season
+            .table
+            .matches
+            .iter()
+            .all(|game| table.matches.contains(game))
+}
+
+// Number of the given seasons each team of `table` took part in.
+pub fn seasons_played(table: &Table, seasons: &[&Season]) -> Vec<usize> {
+    table
+        .teams
+        .iter()
+        .map(|team| {
+            seasons
+                .iter()
+                .filter(|season| season.table.position(&team.name).is_some())
+                .count()
+        })
+        .collect()
+}
+
+pub fn create_season(id: &str, competition: &str, teams: &[String]) -> Season {
+    Season {
+        id: id.to_string(),
+        competition: competition.to_string(),
+        table: tables::create_table(teams),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_archive() -> Archive {
+        let mut first = create_season(
+            "2023",
+            "Allsvenskan",
+            &[String::from("Foo"), String::from("Bar")],
+        );
+        first.table.add_game("Foo", "Bar", 2, 0);
+        first.table.add_game("Bar", "Foo", 1, 1);
+
+        let mut second = create_season(
+            "2024",
+            "Allsvenskan",
+            &[String::from("Foo"), String::from("Baz")],
+        );
+        second.table.add_game("Baz", "Foo", 3, 0);
+
+        let mut archive = Archive::default();
+        archive.add_season(first).unwrap();
+        archive.add_season(second).unwrap();
+        archive
+    }
+
+    #[test]
+    fn test_add_season() {
+        let mut archive = create_test_archive();
+
+        assert!(archive
+            .add_season(create_season("2023", "Allsvenskan", &[]))
+            .is_err());
+        archive
+            .add_season(create_season("2023", "Superettan", &[]))
+            .unwrap();
+        archive
+            .add_season(create_season("2022", "Allsvenskan", &[]))
+            .unwrap();
+
+        let ids: Vec<&str> = archive
+            .seasons
+            .iter()
+            .map(|season| season.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["2022", "2023", "2023", "2024"]);
+        assert_eq!(archive.select("Allsvenskan", &[]).unwrap().len(), 3);
+        assert_eq!(
+            archive.select("Superettan", &["2023"]).unwrap()[0].competition,
+            "Superettan"
+        );
+        assert!(archive.select("Superettan", &["2024"]).is_err());
+        assert!(archive.select("Allsvenskan", &["1999"]).is_err());
+    }
+
+    #[test]
+    fn test_all_time_table() {
+        let archive = create_test_archive();
+        let seasons = archive.select("Allsvenskan", &[]).unwrap();
+        let table = all_time_table(&seasons);
+
+        assert_eq!(table.teams.len(), 3);
+        assert_eq!(table.teams[0].name, "Foo");
+        assert_eq!(table.teams[0].played, 3);
+        assert_eq!(table.teams[0].points, 4);
+        assert_eq!(table.teams[0].goal_difference, -1);
+        assert_eq!(table.teams[1].name, "Baz");
+        assert_eq!(table.teams[1].points, 3);
+        assert_eq!(seasons_played(&table, &seasons), vec![2, 1, 1]);
+    }
+
+    #[test]
+    fn test_head_to_head_across_competitions() {
+        let mut archive = create_test_archive();
+        let mut lower = create_season(
+            "2022",
+            "Superettan",
+            &[String::from("Bar"), String::from("Baz")],
+        );
+        lower.table.add_game("Bar", "Baz", 1, 0);
+        archive.add_season(lower).unwrap();
+
+        let record = archive.head_to_head("Baz", "Bar");
+        assert_eq!(record.meetings.len(), 1);
+        assert_eq!(record.meetings[0].0, "2022 Superettan");
+        assert_eq!(archive.head_to_head("Foo", "Bar").meetings.len(), 2);
+    }
+
+    #[test]
+    fn test_is_season_of() {
+        let archive = create_test_archive();
+        let mut current = archive.seasons[1].table.clone();
+        current.name = String::from("Allsvenskan");
+        current.add_game("Foo", "Baz", 1, 1);
+
+        assert!(is_season_of(&archive.seasons[1], &current));
+        assert!(!is_season_of(&archive.seasons[0], &current));
+
+        current.name = String::from("Superettan");
+        assert!(!is_season_of(&archive.seasons[1], &current));
+    }
+
+    #[test]
+    fn test_parse_archive_entry() {
+        let (id, competition, table_file, results_file) =
+            parse_archive_entry("2024;Allsvenskan;table.txt;results.txt").unwrap();
+
+        assert_eq!(id, "2024");
+        assert_eq!(competition, "Allsvenskan");
+        assert_eq!(table_file, "table.txt");
+        assert_eq!(results_file, Some(String::from("results.txt")));
+        assert_eq!(
+            parse_archive_entry("2023;Allsvenskan;table_2023.txt")
+                .unwrap()
+                .3,
+            None
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Archive line does not contain three or four parts.")]
+    fn test_parse_archive_entry_panic_missing_parts() {
+        parse_archive_entry("2023;Allsvenskan").unwrap();
+    }
+}
@@ -0,0 +1,114 @@
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, Utc, Weekday};
+
+// Kick-off times are entered and stored in Swedish local time (Europe/Stockholm). Sweden
+// follows the EU summer time rules: CEST (UTC+2) from 01:00 UTC on the last Sunday of March
+// until 01:00 UTC on the last Sunday of October, CET (UTC+1) otherwise.
+const CET_OFFSET_SECONDS: i32 = 3600;
+const CEST_OFFSET_SECONDS: i32 = 7200;
+
+pub fn parse_kick_off(kick_off_str: &str) -> Result<NaiveDateTime, &'static str> {
+    let trimmed = kick_off_str.trim();
+
+    if let Ok(date_time) = NaiveDateTime::parse_from_str(trimmed, "%Y-%m-%d %H:%M") {
+        return Ok(date_time);
+    }
+
+    parse_date(trimmed).map(|date| date.and_hms_opt(0, 0, 0).unwrap())
+}
+
+pub fn parse_date(date_str: &str) -> Result<NaiveDate, &'static str> {
+    NaiveDate::parse_from_str(date_str.trim(), "%Y-%m-%d")
+        .map_err(|_| "Cannot parse the date, expected YYYY-MM-DD.")
+}
+
+pub fn parse_weekday(weekday_str: &str) -> Result<Weekday, &'static str> {
+    weekday_str
+        .trim()
+        .parse::<Weekday>()
+        .map_err(|_| "Cannot parse the weekday.")
+}
+
+fn last_sunday(year: i32, month: u32) -> NaiveDate {
+    let first_of_next_month = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)
+    }
+    .unwrap();
+
+    let last_day = first_of_next_month - Duration::days(1);
+    let days_since_sunday = last_day.weekday().num_days_from_sunday() as i64;
+    last_day - Duration::days(days_since_sunday)
+}
+
+pub fn stockholm_offset(local: &NaiveDateTime) -> FixedOffset {
+    let year = local.year();
+    let summer_time_start = last_sunday(year, 3).and_hms_opt(2, 0, 0).unwrap();
+    let summer_time_end = last_sunday(year, 10).and_hms_opt(3, 0, 0).unwrap();
+
+    // The hour that is repeated in October is read as summer time.
+    if *local >= summer_time_start && *local < summer_time_end {
+        FixedOffset::east_opt(CEST_OFFSET_SECONDS).unwrap()
+    } else {
+        FixedOffset::east_opt(CET_OFFSET_SECONDS).unwrap()
+    }
+}
+
+pub fn stockholm_to_utc(local: &NaiveDateTime) -> DateTime<Utc> {
+    let offset_seconds = stockholm_offset(local).local_minus_utc() as i64;
+    DateTime::<Utc>::from_naive_utc_and_offset(*local - Duration::seconds(offset_seconds), Utc)
+}
+
+pub fn format_kick_off(local: &NaiveDateTime) -> String {
+    let with_offset = DateTime::<FixedOffset>::from_naive_utc_and_offset(
+        stockholm_to_utc(local).naive_utc(),
+        stockholm_offset(local),
+    );
+    with_offset.format("%Y-%m-%d %H:%M %:z").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_kick_off() {
+        let kick_off = parse_kick_off("2024-03-30 15:00").unwrap();
+        assert_eq!(kick_off.to_string(), "2024-03-30 15:00:00");
+
+        let date_only = parse_kick_off("2024-03-30").unwrap();
+        assert_eq!(date_only.to_string(), "2024-03-30 00:00:00");
+
+        assert!(parse_kick_off("30/3 15:00").is_err());
+    }
+
+    #[test]
+    fn test_parse_weekday() {
+        assert_eq!(parse_weekday("sun").unwrap(), Weekday::Sun);
+        assert_eq!(parse_weekday("Monday").unwrap(), Weekday::Mon);
+        assert!(parse_weekday("söndag").is_err());
+    }
+
+    #[test]
+    fn test_stockholm_offset() {
+        // Summer time 2024 started on March 31 and ended on October 27.
+        let winter = parse_kick_off("2024-03-30 15:00").unwrap();
+        let summer = parse_kick_off("2024-03-31 15:00").unwrap();
+        let autumn = parse_kick_off("2024-10-27 15:00").unwrap();
+
+        assert_eq!(stockholm_offset(&winter).local_minus_utc(), 3600);
+        assert_eq!(stockholm_offset(&summer).local_minus_utc(), 7200);
+        assert_eq!(stockholm_offset(&autumn).local_minus_utc(), 3600);
+    }
+
+    #[test]
+    fn test_stockholm_to_utc() {
+        let kick_off = parse_kick_off("2024-07-01 19:00").unwrap();
+
+        assert_eq!(
+            stockholm_to_utc(&kick_off).to_rfc3339(),
+            "2024-07-01T17:00:00+00:00"
+        );
+        assert_eq!(format_kick_off(&kick_off), "2024-07-01 19:00 +02:00");
+    }
+}
@@ -0,0 +1,132 @@
+use crate::standings::matches::{Match, Venue};
+use crate::standings::scorers;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CardType {
+    Yellow,
+    // A second yellow card, which means a sending off.
+    SecondYellow,
+    Red,
+}
+
+impl CardType {
+    fn code(&self) -> &'static str {
+        match self {
+            CardType::Yellow => "y",
+            CardType::SecondYellow => "2y",
+            CardType::Red => "r",
+        }
+    }
+}
+
+// A card shown to a player of the team playing at `venue`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Card {
+    pub player: String,
+    pub minute: u32,
+    pub added_time: u32,
+    pub card_type: CardType,
+    pub venue: Venue,
+}
+
+impl Card {
+    pub fn team<'a>(&self, game: &'a Match) -> &'a str {
+        game.side(self.venue).0
+    }
+}
+
+// Parses one card event such as "Berg 55' (y)", "Berg 80' (2y)" or "Ali 12' (r)".
+pub fn parse_card(card_str: &str, venue: Venue) -> Result<Card, &'static str> {
+    let (event, code) = card_str
+        .trim()
+        .strip_suffix(')')
+        .and_then(|rest| rest.rsplit_once('('))
+        .ok_or("Invalid card format, use \"name minute' (y/2y/r)\".")?;
+    let card_type = match code.trim() {
+        "y" => CardType::Yellow,
+        "2y" => CardType::SecondYellow,
+        "r" => CardType::Red,
+        _ => return Err("Unknown card, use y, 2y or r."),
+    };
+
+    let (player, minute_str) = event
+        .trim()
+        .rsplit_once(' ')
+        .ok_or("Invalid card format, use \"name minute' (y/2y/r)\".")?;
+    let (minute, added_time) = scorers::parse_minute(minute_str)?;
+    if player.trim().is_empty() {
+        return Err("The player is missing.");
+    }
+
+    Ok(Card {
+        player: player.trim().to_string(),
+        minute,
+        added_time,
+        card_type,
+        venue,
+    })
+}
+
+// Parses the card field of a result line, "cards:" followed by the home team's cards, then
+// "|" and the away team's cards, e.g. "cards:Berg 55' (y)|Ali 12' (r)".
+pub fn parse_cards(cards_str: &str) -> Result<Vec<Card>, &'static str> {
+    let cards_str = cards_str
+        .trim()
+        .strip_prefix("cards:")
+        .ok_or("The card field must start with \"cards:\".")?;
+    let (home_str, away_str) = cards_str.split_once('|').unwrap_or((cards_str, ""));
+
+    let mut cards = Vec::new();
+    for (side_str, venue) in [(home_str, Venue::Home), (away_str, Venue::Away)] {
+        for card_str in side_str.split(',').filter(|card| !card.trim().is_empty()) {
+            cards.push(parse_card(card_str, venue)?);
+        }
+    }
+    Ok(cards)
+}
+
+// Formats cards as the card field of a result line that `parse_cards` reads back.
+pub fn format_cards(cards: &[Card]) -> String {
+    let side = |venue| {
+        cards
+            .iter()
+            .filter(|card| card.venue == venue)
+            .map(|card| {
+                format!(
+                    "{} {} ({})",
+                    card.player,
+                    scorers::format_minute(card.minute, card.added_time),
+                    card.card_type.code()
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+    format!("cards:{}|{}", side(Venue::Home), side(Venue::Away))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cards() {
+        let cards = parse_cards("cards:Berg 55' (y), Berg 90+1' (2y)|Ali 12' (r)").unwrap();
+
+        assert_eq!(cards.len(), 3);
+        assert_eq!(cards[1].card_type, CardType::SecondYellow);
+        assert_eq!(cards[1].added_time, 1);
+        assert_eq!(cards[2].player, "Ali");
+        assert_eq!(cards[2].venue, Venue::Away);
+        assert_eq!(
+            format_cards(&cards),
+            "cards:Berg 55' (y), Berg 90+1' (2y)|Ali 12' (r)"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown card, use y, 2y or r.")]
+    fn test_parse_card_panic_unknown_card() {
+        parse_card("Berg 55' (blue)", Venue::Home).unwrap();
+    }
+}
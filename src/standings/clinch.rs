@@ -0,0 +1,482 @@
+use std::collections::VecDeque;
+
+use crate::standings::tables::Table;
+use crate::standings::zones::Zones;
+
+// Sets of teams with at most this many remaining fixtures between them are checked by trying
+// every outcome. Larger sets use a network flow relaxation that allows any split of the
+// points of a match, which can only make a clinch appear later, never too early.
+const EXACT_SEARCH_LIMIT: usize = 10;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PositionRange {
+    pub best: usize,
+    pub worst: usize,
+}
+
+// Best and worst possible final position of every team in `table.teams` order. Teams level on
+// points are assumed to be able to finish either way, since goal difference is still open.
+// Points adjustments count, e.g. the halved points of a split. Fails when the remaining
+// fixtures are unknown.
+pub fn position_ranges(table: &Table) -> Result<Vec<PositionRange>, &'static str> {
+    let points: Vec<i32> = table
+        .teams
+        .iter()
+        .map(|team| team.wins as i32 * 3 + team.draws as i32 + team.points_adjustment)
+        .collect();
+    let fixtures: Vec<(usize, usize)> = table
+        .remaining_fixtures()?
+        .iter()
+        .filter_map(|fixture| {
+            let home = table
+                .teams
+                .iter()
+                .position(|team| team.name == fixture.home_team)?;
+            let away = table
+                .teams
+                .iter()
+                .position(|team| team.name == fixture.away_team)?;
+            Some((home, away))
+        })
+        .collect();
+
+    Ok((0..table.teams.len())
+        .map(|team| PositionRange {
+            best: best_position(team, &points, &fixtures),
+            worst: worst_position(team, &points, &fixtures),
+        })
+        .collect())
+}
+
+// One letter flag per team for what it has mathematically secured or lost:
+// C champions, E European spot, S safe from the playoff and relegation, R relegated.
+pub fn clinch_flags(ranges: &[PositionRange], zones: &Zones) -> Vec<String> {
+    let team_count = ranges.len();
+
+    ranges
+        .iter()
+        .map(|range| {
+            if range.worst <= zones.title_spots {
+                String::from("C")
+            } else if range.worst <= zones.europe_spots {
+                String::from("E")
+            } else if range.best >= zones.first_relegation_position(team_count) {
+                String::from("R")
+            } else if range.worst <= zones.last_safe_position(team_count) {
+                String::from("S")
+            } else {
+                String::new()
+            }
+        })
+        .collect()
+}
+
+// The team wins all its remaining matches; count the teams that cannot be kept at or below
+// its points.
+fn best_position(team: usize, points: &[i32], fixtures: &[(usize, usize)]) -> usize {
+    let remaining = fixtures
+        .iter()
+        .filter(|(home, away)| *home == team || *away == team)
+        .count() as i32;
+    let max_points = points[team] + 3 * remaining;
+
+    let mut candidates: Vec<usize> = (0..points.len())
+        .filter(|other| *other != team && points[*other] <= max_points)
+        .collect();
+    candidates.sort_by_key(|other| points[*other]);
+    let surely_above = points.len() - 1 - candidates.len();
+
+    let caps: Vec<i32> = points.iter().map(|other| max_points - other).collect();
+    let kept_below = largest_feasible_set(&candidates, &|set: &[usize]| {
+        can_stay_below(set, &caps, fixtures)
+    });
+
+    1 + surely_above + (candidates.len() - kept_below)
+}
+
+// The team loses all its remaining matches; count the teams that can reach its points.
+fn worst_position(team: usize, points: &[i32], fixtures: &[(usize, usize)]) -> usize {
+    let min_points = points[team];
+    let mut remaining_games = vec![0; points.len()];
+    for (home, away) in fixtures {
+        remaining_games[*home] += 1;
+        remaining_games[*away] += 1;
+    }
+
+    let mut candidates: Vec<usize> = (0..points.len())
+        .filter(|other| {
+            *other != team && points[*other] + 3 * remaining_games[*other] >= min_points
+        })
+        .collect();
+    candidates.sort_by_key(|other| -points[*other]);
+
+    let above = largest_feasible_set(&candidates, &|set: &[usize]| {
+        can_all_reach(set, points, min_points, fixtures)
+    });
+
+    1 + above
+}
+
+// Size of the largest subset of `candidates` accepted by `feasible`. Feasibility must be
+// preserved when teams are removed from a set, which holds for both checks below since a
+// removed team's matches against the set then count as easy wins or defeats.
+fn largest_feasible_set(candidates: &[usize], feasible: &dyn Fn(&[usize]) -> bool) -> usize {
+    if feasible(candidates) {
+        return candidates.len();
+    }
+
+    let mut best = 0;
+    let mut chosen = Vec::new();
+    search_sets(candidates, 0, &mut chosen, &mut best, feasible);
+    best
+}
+
+fn search_sets(
+    candidates: &[usize],
+    index: usize,
+    chosen: &mut Vec<usize>,
+    best: &mut usize,
+    feasible: &dyn Fn(&[usize]) -> bool,
+) {
+    if chosen.len() + (candidates.len() - index) <= *best {
+        return;
+    }
+    if index == candidates.len() {
+        *best = chosen.len();
+        return;
+    }
+
+    chosen.push(candidates[index]);
+    if feasible(chosen) {
+        search_sets(candidates, index + 1, chosen, best, feasible);
+    }
+    chosen.pop();
+    search_sets(candidates, index + 1, chosen, best, feasible);
+}
+
+fn internal_fixtures(set: &[usize], fixtures: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    fixtures
+        .iter()
+        .filter(|(home, away)| set.contains(home) && set.contains(away))
+        .copied()
+        .collect()
+}
+
+// Can every team in the set finish on at most `caps[team]` more points? Matches against teams
+// outside the set are lost, matches inside the set must still hand out points.
+fn can_stay_below(set: &[usize], caps: &[i32], fixtures: &[(usize, usize)]) -> bool {
+    let games = internal_fixtures(set, fixtures);
+
+    if games.len() <= EXACT_SEARCH_LIMIT {
+        let mut gained = vec![0; caps.len()];
+        return exact_search(&games, 0, &mut gained, &|gained: &[i32]| {
+            set.iter().all(|team| gained[*team] <= caps[*team])
+        });
+    }
+
+    // Every match gives at least two points in total to its teams.
+    let mut network = FlowNetwork::new(2 + games.len() + set.len());
+    let (source, sink) = (0, 1);
+    for (i, (home, away)) in games.iter().enumerate() {
+        network.add_edge(source, 2 + i, 2);
+        network.add_edge(2 + i, team_node(set, *home, games.len()), 2);
+        network.add_edge(2 + i, team_node(set, *away, games.len()), 2);
+    }
+    for (i, team) in set.iter().enumerate() {
+        network.add_edge(2 + games.len() + i, sink, caps[*team].max(0));
+    }
+
+    network.max_flow(source, sink) == 2 * games.len() as i32
+}
+
+// Can every team in the set reach `target` points? Matches against teams outside the set are
+// won, matches inside the set decide who gets the points.
+fn can_all_reach(set: &[usize], points: &[i32], target: i32, fixtures: &[(usize, usize)]) -> bool {
+    let games = internal_fixtures(set, fixtures);
+
+    let mut needs = vec![0; points.len()];
+    for team in set {
+        let outside_wins = fixtures
+            .iter()
+            .filter(|(home, away)| {
+                (home == team && !set.contains(away)) || (away == team && !set.contains(home))
+            })
+            .count() as i32;
+        needs[*team] = (target - points[*team] - 3 * outside_wins).max(0);
+    }
+
+    if games.len() <= EXACT_SEARCH_LIMIT {
+        let mut gained = vec![0; points.len()];
+        return exact_search(&games, 0, &mut gained, &|gained: &[i32]| {
+            set.iter().all(|team| gained[*team] >= needs[*team])
+        });
+    }
+
+    // Every match gives at most three points in total to its teams.
+    let mut network = FlowNetwork::new(2 + games.len() + set.len());
+    let (source, sink) = (0, 1);
+    for (i, (home, away)) in games.iter().enumerate() {
+        network.add_edge(source, 2 + i, 3);
+        network.add_edge(2 + i, team_node(set, *home, games.len()), 3);
+        network.add_edge(2 + i, team_node(set, *away, games.len()), 3);
+    }
+    let mut total_need = 0;
+    for (i, team) in set.iter().enumerate() {
+        network.add_edge(2 + games.len() + i, sink, needs[*team]);
+        total_need += needs[*team];
+    }
+
+    network.max_flow(source, sink) == total_need
+}
+
+fn team_node(set: &[usize], team: usize, game_count: usize) -> usize {
+    2 + game_count + set.iter().position(|member| *member == team).unwrap()
+}
+
+// Tries every home win, draw and away win combination of the games.
+fn exact_search(
+    games: &[(usize, usize)],
+    index: usize,
+    gained: &mut Vec<i32>,
+    accept: &dyn Fn(&[i32]) -> bool,
+) -> bool {
+    if index == games.len() {
+        return accept(gained);
+    }
+
+    let (home, away) = games[index];
+    for (home_points, away_points) in [(3, 0), (1, 1), (0, 3)] {
+        gained[home] += home_points;
+        gained[away] += away_points;
+        let found = exact_search(games, index + 1, gained, accept);
+        gained[home] -= home_points;
+        gained[away] -= away_points;
+        if found {
+            return true;
+        }
+    }
+
+    false
+}
+
+struct FlowNetwork {
+    edges: Vec<Vec<usize>>,
+    targets: Vec<usize>,
+    capacities: Vec<i32>,
+}
+
+impl FlowNetwork {
+    fn new(node_count: usize) -> FlowNetwork {
+        FlowNetwork {
+            edges: vec![Vec::new(); node_count],
+            targets: Vec::new(),
+            capacities: Vec::new(),
+        }
+    }
+
+    // Edges are stored in pairs so that `edge ^ 1` is the reverse edge.
+    fn add_edge(&mut self, from: usize, to: usize, capacity: i32) {
+        self.edges[from].push(self.targets.len());
+        self.targets.push(to);
+        self.capacities.push(capacity);
+        self.edges[to].push(self.targets.len());
+        self.targets.push(from);
+        self.capacities.push(0);
+    }
+
+    // Edmonds-Karp.
+    fn max_flow(&mut self, source: usize, sink: usize) -> i32 {
+        let mut flow = 0;
+
+        loop {
+            let mut previous_edge: Vec<Option<usize>> = vec![None; self.edges.len()];
+            let mut queue = VecDeque::from([source]);
+            while let Some(node) = queue.pop_front() {
+                for edge in &self.edges[node] {
+                    let target = self.targets[*edge];
+                    if self.capacities[*edge] > 0
+                        && target != source
+                        && previous_edge[target].is_none()
+                    {
+                        previous_edge[target] = Some(*edge);
+                        queue.push_back(target);
+                    }
+                }
+            }
+
+            if previous_edge[sink].is_none() {
+                return flow;
+            }
+
+            let mut bottleneck = i32::MAX;
+            let mut node = sink;
+            while let Some(edge) = previous_edge[node] {
+                bottleneck = bottleneck.min(self.capacities[edge]);
+                node = self.targets[edge ^ 1];
+            }
+
+            let mut node = sink;
+            while let Some(edge) = previous_edge[node] {
+                self.capacities[edge] -= bottleneck;
+                self.capacities[edge ^ 1] += bottleneck;
+                node = self.targets[edge ^ 1];
+            }
+
+            flow += bottleneck;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standings::fixtures;
+    use crate::standings::tables;
+    use crate::standings::team::TeamStats;
+
+    fn create_test_table(records: &[(&str, u32, u32)], schedule: &[(&str, &str)]) -> Table {
+        let teams = records
+            .iter()
+            .map(|(name, wins, draws)| TeamStats {
+                name: name.to_string(),
+                wins: *wins,
+                draws: *draws,
+                ..Default::default()
+            })
+            .collect();
+        let mut table = Table {
+            teams,
+            fixtures: schedule
+                .iter()
+                .map(|(home, away)| fixtures::create_fixture(home, away))
+                .collect(),
+            // The records are played before the schedule, as in a section after a split.
+            carried_over: true,
+            ..Default::default()
+        };
+        table.update_table();
+        table
+    }
+
+    #[test]
+    fn test_champion_clinched() {
+        let table = create_test_table(
+            &[("FC Test", 4, 0), ("Foo", 2, 0), ("Bar", 1, 0)],
+            &[("Foo", "Bar"), ("Bar", "FC Test")],
+        );
+
+        let ranges = position_ranges(&table).unwrap();
+        assert_eq!(ranges[0], PositionRange { best: 1, worst: 1 });
+        assert_eq!(ranges[1], PositionRange { best: 2, worst: 3 });
+        assert_eq!(ranges[2], PositionRange { best: 2, worst: 3 });
+    }
+
+    #[test]
+    fn test_head_to_head_between_contenders() {
+        // Foo and Bar can each reach 9 points, but not both since they meet each other.
+        // Naive maximum points would say FC Test can still drop to third.
+        let table = create_test_table(
+            &[
+                ("FC Test", 3, 0),
+                ("Foo", 2, 0),
+                ("Bar", 2, 0),
+                ("Baz", 0, 0),
+            ],
+            &[("Foo", "Bar"), ("Baz", "FC Test")],
+        );
+
+        let ranges = position_ranges(&table).unwrap();
+        assert_eq!(table.teams[0].name, "FC Test");
+        assert_eq!(ranges[0].worst, 2);
+        assert_eq!(ranges[3], PositionRange { best: 4, worst: 4 });
+    }
+
+    #[test]
+    fn test_draw_can_lift_two_teams() {
+        // Foo and Bar both reach FC Test's 7 points with a draw between them.
+        let table = create_test_table(
+            &[("FC Test", 2, 1), ("Foo", 2, 0), ("Bar", 2, 0)],
+            &[("Foo", "Bar")],
+        );
+
+        let ranges = position_ranges(&table).unwrap();
+        assert_eq!(ranges[0].worst, 3);
+    }
+
+    #[test]
+    fn test_halved_points_after_split() {
+        // A section after a split with halved points: FC Test 12 -> 6, Foo 8 -> 4.
+        let mut table =
+            create_test_table(&[("FC Test", 4, 0), ("Foo", 2, 2)], &[("Foo", "FC Test")]);
+        table.teams[0].points_adjustment = -6;
+        table.teams[1].points_adjustment = -4;
+        table.update_table();
+
+        // Foo can still pass FC Test, which the points before halving would rule out.
+        let ranges = position_ranges(&table).unwrap();
+        assert_eq!(table.teams[0].points, 6);
+        assert_eq!(ranges[0], PositionRange { best: 1, worst: 2 });
+        assert_eq!(ranges[1], PositionRange { best: 1, worst: 2 });
+    }
+
+    #[test]
+    fn test_unknown_remaining_schedule() {
+        // Records read from a table file with the full season schedule: the games behind the
+        // records are not in the log, so nothing can be clinched from the schedule.
+        let mut table = create_test_table(
+            &[("FC Test", 1, 0), ("Foo", 0, 0)],
+            &[("FC Test", "Foo"), ("Foo", "FC Test")],
+        );
+        table.carried_over = false;
+
+        assert!(position_ranges(&table).is_err());
+    }
+
+    #[test]
+    fn test_flow_relaxation_for_many_games() {
+        let team_names: Vec<String> = (0..6).map(|i| format!("Team {}", i)).collect();
+        let mut table = tables::create_table(&team_names);
+        table.update_table();
+
+        // Nobody has played: every team can still finish anywhere.
+        let ranges = position_ranges(&table).unwrap();
+        assert!(ranges
+            .iter()
+            .all(|range| *range == PositionRange { best: 1, worst: 6 }));
+    }
+
+    #[test]
+    fn test_clinch_flags() {
+        let zones = Zones {
+            title_spots: 1,
+            europe_spots: 2,
+            playoff_spots: 1,
+            relegation_spots: 1,
+        };
+        let ranges = vec![
+            PositionRange { best: 1, worst: 1 },
+            PositionRange { best: 2, worst: 2 },
+            PositionRange { best: 3, worst: 4 },
+            PositionRange { best: 3, worst: 5 },
+            PositionRange { best: 4, worst: 6 },
+            PositionRange { best: 6, worst: 6 },
+        ];
+
+        assert_eq!(
+            clinch_flags(&ranges, &zones),
+            vec!["C", "E", "S", "", "", "R"]
+        );
+    }
+
+    #[test]
+    fn test_max_flow() {
+        let mut network = FlowNetwork::new(4);
+        network.add_edge(0, 2, 3);
+        network.add_edge(0, 3, 2);
+        network.add_edge(2, 1, 2);
+        network.add_edge(3, 1, 5);
+        network.add_edge(2, 3, 1);
+
+        assert_eq!(network.max_flow(0, 1), 5);
+    }
+}
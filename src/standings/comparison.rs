@@ -0,0 +1,275 @@
+use crate::standings::archive::Season;
+use crate::standings::tables::Table;
+use crate::standings::team::TeamStats;
+
+// A team's position and record in each of the two seasons. `None` when the team did not play
+// in that season's competition, e.g. after promotion or relegation.
+#[derive(Clone, Debug)]
+pub struct TeamComparison {
+    pub name: String,
+    pub first: Option<(usize, TeamStats)>,
+    pub second: Option<(usize, TeamStats)>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SeasonComparison {
+    pub first_id: String,
+    pub second_id: String,
+    // The round both seasons are compared after, `None` when neither has a match log.
+    pub round: Option<u32>,
+    // Teams of the second season in its table order, then teams only in the first season.
+    pub teams: Vec<TeamComparison>,
+}
+
+impl SeasonComparison {
+    pub fn print(&self) {
+        match self.round {
+            Some(round) => println!(
+                "{} compared to {} after round {}",
+                self.second_id, self.first_id, round
+            ),
+            None => println!("{} compared to {}", self.second_id, self.first_id),
+        }
+
+        let column_width: usize = self
+            .teams
+            .iter()
+            .map(|team| team.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(4);
+
+        println!(
+            "{:<width$} {:>3} {:>4}  {:>3} {:>4}  {:>3} {:>4}  {:>3} {:>4}",
+            "Club",
+            "Pos",
+            "+/-",
+            "P",
+            "+/-",
+            "GM",
+            "+/-",
+            "GC",
+            "+/-",
+            width = column_width
+        );
+
+        for team in &self.teams {
+            let row = match (&team.first, &team.second) {
+                (Some((first_position, first)), Some((second_position, second))) => format!(
+                    "{:>3} {:>4}  {:>3} {:>4}  {:>3} {:>4}  {:>3} {:>4}",
+                    second_position,
+                    signed(*first_position as i32 - *second_position as i32),
+                    second.points,
+                    signed(second.points as i32 - first.points as i32),
+                    second.goal_scored,
+                    signed(second.goal_scored - first.goal_scored),
+                    second.goal_against,
+                    signed(second.goal_against - first.goal_against)
+                ),
+                (None, Some((second_position, second))) => format!(
+                    "{:>3} {:>4}  {:>3} {:>4}  {:>3} {:>4}  {:>3} {:>4}",
+                    second_position,
+                    "new",
+                    second.points,
+                    "",
+                    second.goal_scored,
+                    "",
+                    second.goal_against,
+                    ""
+                ),
+                (Some(_), None) => format!("{:>3} {:>4}", "-", "left"),
+                (None, None) => String::new(),
+            };
+
+            println!("{:<width$} {}", team.name, row, width = column_width);
+        }
+    }
+}
+
+// Compares two seasons at the same stage. Seasons with a match log are taken back to the last
+// round both have reached. A season without one can only be compared as it stands, so the
+// other season is cut to its number of games played, or must be at that stage already.
+pub fn compare_seasons(first: &Season, second: &Season) -> Result<SeasonComparison, &'static str> {
+    let round = comparison_round(&first.table, &second.table)?;
+
+    let first_table = table_at(&first.table, round);
+    let second_table = table_at(&second.table, round);
+
+    let mut teams: Vec<TeamComparison> = second_table
+        .teams
+        .iter()
+        .enumerate()
+        .map(|(index, team)| TeamComparison {
+            name: team.name.clone(),
+            first: placing(&first_table, &team.name),
+            second: Some((index + 1, team.clone())),
+        })
+        .collect();
+
+    teams.extend(
+        first_table
+            .teams
+            .iter()
+            .filter(|team| second_table.position(&team.name).is_none())
+            .map(|team| TeamComparison {
+                name: team.name.clone(),
+                first: placing(&first_table, &team.name),
+                second: None,
+            }),
+    );
+
+    Ok(SeasonComparison {
+        first_id: first.id.clone(),
+        second_id: second.id.clone(),
+        round,
+        teams,
+    })
+}
+
+// The round both seasons are compared after, or `None` when both are compared as they stand.
+fn comparison_round(first: &Table, second: &Table) -> Result<Option<u32>, &'static str> {
+    const NOT_SAME_STAGE: &str =
+        "The seasons are not at the same stage and one has no match log to go back in.";
+
+    match (
+        first.has_complete_match_log(),
+        second.has_complete_match_log(),
+    ) {
+        (true, true) => Ok(Some(first.last_round().min(second.last_round()))),
+        (true, false) | (false, true) => {
+            let (logged, unlogged) = if first.has_complete_match_log() {
+                (first, second)
+            } else {
+                (second, first)
+            };
+            match games_played(unlogged) {
+                Some(played) if played <= logged.last_round() => Ok(Some(played)),
+                _ => Err(NOT_SAME_STAGE),
+            }
+        }
+        (false, false) => match (games_played(first), games_played(second)) {
+            (Some(first_played), Some(second_played)) if first_played == second_played => Ok(None),
+            _ => Err(NOT_SAME_STAGE),
+        },
+    }
+}
+
+// The number of games every team of the table has played, `None` if they differ.
+fn games_played(table: &Table) -> Option<u32> {
+    let mut played = table
+        .teams
+        .iter()
+        .map(|team| team.wins + team.draws + team.defeats);
+    let first = played.next()?;
+    played.all(|games| games == first).then_some(first)
+}
+
+// The table after the round, or as it stands for a table without a match log.
+fn table_at(table: &Table, round: Option<u32>) -> Table {
+    match round {
+        Some(round) if table.has_complete_match_log() => table.standings_after_round(round),
+        _ => {
+            let mut current = table.clone();
+            current.update_table();
+            current
+        }
+    }
+}
+
+fn placing(table: &Table, team_name: &str) -> Option<(usize, TeamStats)> {
+    let position = table.position(team_name)?;
+    Some((position, table.teams[position - 1].clone()))
+}
+
+fn signed(value: i32) -> String {
+    if value > 0 {
+        format!("+{}", value)
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standings::archive;
+
+    #[test]
+    fn test_compare_seasons_at_same_round() {
+        let mut first = archive::create_season(
+            "2023",
+            "Allsvenskan",
+            &[String::from("Foo"), String::from("Bar")],
+        );
+        first.table.add_game("Foo", "Bar", 2, 0);
+        first.table.add_game("Bar", "Foo", 1, 1);
+
+        let mut second = archive::create_season(
+            "2024",
+            "Allsvenskan",
+            &[String::from("Foo"), String::from("Baz")],
+        );
+        second.table.add_game("Baz", "Foo", 3, 0);
+
+        let comparison = compare_seasons(&first, &second).unwrap();
+
+        assert_eq!(comparison.round, Some(1));
+        assert_eq!(comparison.teams.len(), 3);
+        assert_eq!(comparison.teams[0].name, "Baz");
+        assert!(comparison.teams[0].first.is_none());
+
+        let foo = &comparison.teams[1];
+        let (first_position, first_stats) = foo.first.as_ref().unwrap();
+        let (second_position, second_stats) = foo.second.as_ref().unwrap();
+        assert_eq!((*first_position, first_stats.points), (1, 3));
+        assert_eq!((*second_position, second_stats.points), (2, 0));
+
+        assert_eq!(comparison.teams[2].name, "Bar");
+        assert!(comparison.teams[2].second.is_none());
+    }
+
+    #[test]
+    fn test_compare_seasons_without_match_log() {
+        // Records as read from a table file, after one game each.
+        let mut first = archive::create_season("2023", "Allsvenskan", &[]);
+        first.table.teams = vec![
+            TeamStats {
+                name: String::from("Foo"),
+                wins: 1,
+                goal_scored: 3,
+                ..Default::default()
+            },
+            TeamStats {
+                name: String::from("Bar"),
+                defeats: 1,
+                goal_against: 3,
+                ..Default::default()
+            },
+        ];
+        let mut second = archive::create_season(
+            "2024",
+            "Allsvenskan",
+            &[String::from("Foo"), String::from("Bar")],
+        );
+        second.table.add_game("Bar", "Foo", 1, 0);
+        second.table.add_game("Foo", "Bar", 2, 2);
+
+        let comparison = compare_seasons(&first, &second).unwrap();
+
+        assert_eq!(comparison.round, Some(1));
+        assert_eq!(comparison.teams[0].name, "Bar");
+        assert_eq!(comparison.teams[0].first.as_ref().unwrap().0, 2);
+        assert_eq!(comparison.teams[0].second.as_ref().unwrap().1.played, 1);
+        assert_eq!(comparison.teams[1].first.as_ref().unwrap().1.points, 3);
+        assert_eq!(signed(3), "+3");
+        assert_eq!(signed(-2), "-2");
+
+        // The 2023 records cannot be taken back to the stage of the 2024 season.
+        first.table.teams[0].wins = 10;
+        first.table.teams[1].defeats = 10;
+        assert_eq!(
+            compare_seasons(&first, &second).err(),
+            Some("The seasons are not at the same stage and one has no match log to go back in.")
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use crate::standings::split;
+use crate::standings::split::SplitRules;
+use crate::standings::tables::Table;
+use crate::standings::zones::Zones;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Competition {
+    pub name: String,
+    pub zones: Zones,
+    // The division teams are relegated to and promoted from.
+    pub lower_division: Option<String>,
+    // The table the division is loaded from.
+    pub table_file: Option<String>,
+    // Splits the division into a top and a bottom section late in the season.
+    pub split: Option<SplitRules>,
+}
+
+// Next season's team lists of a division and the division below it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rollover {
+    pub upper_teams: Vec<String>,
+    pub lower_teams: Vec<String>,
+    pub promoted: Vec<String>,
+    pub relegated: Vec<String>,
+}
+
+impl Rollover {
+    pub fn print(&self, upper_name: &str, lower_name: &str) {
+        println!("Promoted to {}: {}", upper_name, self.promoted.join(", "));
+        println!("Relegated to {}: {}", lower_name, self.relegated.join(", "));
+        println!(
+            "{} next season: {}",
+            upper_name,
+            self.upper_teams.join(", ")
+        );
+        println!(
+            "{} next season: {}",
+            lower_name,
+            self.lower_teams.join(", ")
+        );
+    }
+}
+
+impl Competition {
+    // The relegation playoffs as (team of this division, team of the lower division). The
+    // best placed playoff team of this division meets the best placed lower division team
+    // that missed direct promotion.
+    pub fn playoff_pairs(&self, upper: &Table, lower: &Table) -> Vec<(String, String)> {
+        let first_playoff = self.zones.last_safe_position(upper.teams.len());
+        (0..self.zones.playoff_spots)
+            .filter_map(|index| {
+                let upper_team = upper.teams.get(first_playoff + index)?;
+                let lower_team = lower.teams.get(self.zones.relegation_spots + index)?;
+                Some((upper_team.name.clone(), lower_team.name.clone()))
+            })
+            .collect()
+    }
+
+    // Applies direct relegation and promotion, as many teams each way, and the playoff
+    // results given as the winner of every pair in `playoff_pairs`. Both tables must be in
+    // final order.
+    pub fn rollover(
+        &self,
+        upper: &Table,
+        lower: &Table,
+        playoff_winners: &[String],
+    ) -> Result<Rollover, &'static str> {
+        let pairs = self.playoff_pairs(upper, lower);
+        if playoff_winners.len() != pairs.len() {
+            return Err("One playoff winner is needed for every playoff.");
+        }
+
+        let first_relegated = self.zones.first_relegation_position(upper.teams.len()) - 1;
+        let mut relegated: Vec<String> = upper.teams[first_relegated..]
+            .iter()
+            .map(|team| team.name.clone())
+            .collect();
+        let mut promoted: Vec<String> = lower
+            .teams
+            .iter()
+            .take(self.zones.relegation_spots)
+            .map(|team| team.name.clone())
+            .collect();
+
+        for ((upper_team, lower_team), winner) in pairs.iter().zip(playoff_winners) {
+            if winner == lower_team {
+                promoted.push(lower_team.clone());
+                relegated.push(upper_team.clone());
+            } else if winner != upper_team {
+                return Err("The playoff winner must be one of the two teams.");
+            }
+        }
+
+        let upper_teams = upper
+            .teams
+            .iter()
+            .map(|team| team.name.clone())
+            .filter(|name| !relegated.contains(name))
+            .chain(promoted.iter().cloned())
+            .collect();
+        let lower_teams = lower
+            .teams
+            .iter()
+            .map(|team| team.name.clone())
+            .filter(|name| !promoted.contains(name))
+            .chain(relegated.iter().cloned())
+            .collect();
+
+        Ok(Rollover {
+            upper_teams,
+            lower_teams,
+            promoted,
+            relegated,
+        })
+    }
+}
+
+// A competition config line: "name;title spots;Europe spots;playoff spots;relegation spots;
+// lower division;table file;split". The lower division, table file and split are optional,
+// see `split::parse_split_rules` for the split.
+pub fn parse_competition(competition_str: &str) -> Result<Competition, &'static str> {
+    let parts: Vec<&str> = competition_str.split(';').map(|part| part.trim()).collect();
+
+    if parts.len() < 5 || parts.len() > 8 {
+        return Err("Competition line does not contain five to eight parts.");
+    }
+    if parts[0].is_empty() {
+        return Err("Competition name is missing.");
+    }
+
+    let spots: Vec<usize> = parts[1..5]
+        .iter()
+        .map(|part| part.parse::<usize>())
+        .collect::<Result<Vec<usize>, _>>()
+        .map_err(|_| "Could not parse the number of zone spots.")?;
+    let optional_part = |index: usize| {
+        parts
+            .get(index)
+            .filter(|part| !part.is_empty())
+            .map(|part| part.to_string())
+    };
+
+    Ok(Competition {
+        name: parts[0].to_string(),
+        zones: Zones {
+            title_spots: spots[0],
+            europe_spots: spots[1],
+            playoff_spots: spots[2],
+            relegation_spots: spots[3],
+        },
+        lower_division: optional_part(5),
+        table_file: optional_part(6),
+        split: optional_part(7)
+            .map(|split_str| split::parse_split_rules(&split_str))
+            .transpose()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standings::tables;
+
+    fn create_named_table(names: &[&str]) -> Table {
+        let team_names: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+        tables::create_table(&team_names)
+    }
+
+    fn create_test_competition() -> Competition {
+        parse_competition("Top;1;1;1;1;Bottom").unwrap()
+    }
+
+    #[test]
+    fn test_parse_competition() {
+        let competition = parse_competition("Allsvenskan;1;3;1;2;Superettan;table.txt").unwrap();
+
+        assert_eq!(competition.name, "Allsvenskan");
+        assert_eq!(competition.zones, Zones::default());
+        assert_eq!(competition.lower_division, Some(String::from("Superettan")));
+        assert_eq!(competition.table_file, Some(String::from("table.txt")));
+        assert_eq!(competition.split, None);
+        assert_eq!(
+            parse_competition("Premiership;1;2;1;1;Championship;;33/6/full")
+                .unwrap()
+                .split
+                .map(|split| split.after_round),
+            Some(33)
+        );
+        assert_eq!(
+            parse_competition("Superettan;1;0;2;2")
+                .unwrap()
+                .lower_division,
+            None
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Could not parse the number of zone spots.")]
+    fn test_parse_competition_panic_incorrect_spots() {
+        parse_competition("Allsvenskan;1;x;1;2;Superettan").unwrap();
+    }
+
+    #[test]
+    fn test_rollover() {
+        let competition = create_test_competition();
+        let upper = create_named_table(&["A", "B", "C", "D"]);
+        let lower = create_named_table(&["E", "F", "G"]);
+
+        assert_eq!(
+            competition.playoff_pairs(&upper, &lower),
+            vec![(String::from("C"), String::from("F"))]
+        );
+
+        let stays = competition
+            .rollover(&upper, &lower, &[String::from("C")])
+            .unwrap();
+        assert_eq!(stays.upper_teams, vec!["A", "B", "C", "E"]);
+        assert_eq!(stays.lower_teams, vec!["F", "G", "D"]);
+
+        let goes_down = competition
+            .rollover(&upper, &lower, &[String::from("F")])
+            .unwrap();
+        assert_eq!(goes_down.promoted, vec!["E", "F"]);
+        assert_eq!(goes_down.relegated, vec!["D", "C"]);
+        assert_eq!(goes_down.upper_teams, vec!["A", "B", "E", "F"]);
+        assert_eq!(goes_down.lower_teams, vec!["G", "D", "C"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "The playoff winner must be one of the two teams.")]
+    fn test_rollover_panic_unknown_winner() {
+        let competition = create_test_competition();
+        let upper = create_named_table(&["A", "B", "C", "D"]);
+        let lower = create_named_table(&["E", "F", "G"]);
+
+        competition
+            .rollover(&upper, &lower, &[String::from("A")])
+            .unwrap();
+    }
+}
@@ -0,0 +1,364 @@
+use crate::standings::history::xml_escape;
+use crate::standings::random::Rng;
+use crate::standings::tie::{Tie, TieOutcome, TieRules};
+
+const SVG_COLUMN_WIDTH: usize = 220;
+const SVG_BOX_WIDTH: usize = 180;
+const SVG_BOX_HEIGHT: usize = 40;
+const SVG_SLOT_HEIGHT: usize = 56;
+const SVG_MARGIN: usize = 30;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DrawMode {
+    // The best remaining seed meets the worst, the second best the second worst and so on.
+    Seeded,
+    // Open draw from the given seed.
+    Random(u64),
+}
+
+// One match of a cup round. A pairing without an away team is a bye.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pairing {
+    pub home_team: String,
+    pub away_team: Option<String>,
+    pub tie: Option<Tie>,
+}
+
+impl Pairing {
+    pub fn winner(&self, rules: &TieRules) -> Option<String> {
+        if self.away_team.is_none() {
+            return Some(self.home_team.clone());
+        }
+
+        match self.tie.as_ref()?.outcome(rules) {
+            TieOutcome::Winner(winner, _) => Some(winner),
+            TieOutcome::Undecided => None,
+        }
+    }
+
+    fn score(&self) -> String {
+        let Some(tie) = &self.tie else {
+            return String::from("-");
+        };
+
+        let (home_score, away_score) = tie.aggregate(true);
+        let mut score = format!("{}-{}", home_score, away_score);
+        if tie.went_to_extra_time() {
+            score.push_str(" aet");
+        }
+        if let Some((home_penalties, away_penalties)) = tie.penalties {
+            score.push_str(&format!(" ({}-{} p)", home_penalties, away_penalties));
+        }
+        score
+    }
+}
+
+// A knockout cup with a new draw before every round.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cup {
+    pub name: String,
+    // Seeding order, best seed first.
+    pub entrants: Vec<String>,
+    pub rules: TieRules,
+    pub rounds: Vec<Vec<Pairing>>,
+}
+
+impl Cup {
+    // The teams left for the next draw.
+    pub fn remaining_teams(&self) -> Result<Vec<String>, &'static str> {
+        match self.rounds.last() {
+            None => Ok(self.entrants.clone()),
+            Some(round) => round
+                .iter()
+                .map(|pairing| pairing.winner(&self.rules))
+                .collect::<Option<Vec<String>>>()
+                .ok_or("The current round is not finished."),
+        }
+    }
+
+    pub fn winner(&self) -> Option<String> {
+        let last_round = self.rounds.last()?;
+        if last_round.len() != 1 || last_round[0].away_team.is_none() {
+            return None;
+        }
+        last_round[0].winner(&self.rules)
+    }
+
+    // Draws the next round from the remaining teams. When their number is not a power of two
+    // the best seeds, or the first teams drawn, get a bye so the round after is complete.
+    pub fn draw(&mut self, mode: DrawMode) -> Result<(), &'static str> {
+        let mut teams = self.remaining_teams()?;
+        if teams.len() < 2 {
+            return Err("The cup is already decided.");
+        }
+
+        match mode {
+            DrawMode::Seeded => teams.sort_by_key(|team| self.seed(team)),
+            DrawMode::Random(seed) => {
+                Rng::for_stream(seed, self.rounds.len() as u64).shuffle(&mut teams)
+            }
+        }
+
+        let byes = teams.len().next_power_of_two() - teams.len();
+        let mut round: Vec<Pairing> = teams[..byes]
+            .iter()
+            .map(|team| Pairing {
+                home_team: team.clone(),
+                away_team: None,
+                tie: None,
+            })
+            .collect();
+
+        let playing = &teams[byes..];
+        let half = playing.len() / 2;
+        for index in 0..half {
+            let (home_team, away_team) = match mode {
+                DrawMode::Seeded => (&playing[index], &playing[playing.len() - 1 - index]),
+                DrawMode::Random(_) => (&playing[2 * index], &playing[2 * index + 1]),
+            };
+            round.push(Pairing {
+                home_team: home_team.clone(),
+                away_team: Some(away_team.clone()),
+                tie: None,
+            });
+        }
+
+        self.rounds.push(round);
+        Ok(())
+    }
+
+    // Records the result of a drawn match of the current round.
+    pub fn add_result(&mut self, tie: Tie) -> Result<(), &'static str> {
+        let round = self.rounds.last_mut().ok_or("No round has been drawn.")?;
+        let pairing = round
+            .iter_mut()
+            .find(|pairing| {
+                pairing.home_team == tie.team1()
+                    && pairing.away_team.as_deref() == Some(tie.team2())
+            })
+            .ok_or("The match is not in the current round.")?;
+
+        if pairing.tie.is_some() {
+            return Err("The match has already been played.");
+        }
+        if let TieOutcome::Undecided = tie.outcome(&self.rules) {
+            return Err("The match needs a winner.");
+        }
+
+        pairing.tie = Some(tie);
+        Ok(())
+    }
+
+    pub fn print(&self) {
+        println!("{}", self.name);
+
+        let column_width: usize = self
+            .entrants
+            .iter()
+            .map(|team| team.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        for (index, round) in self.rounds.iter().enumerate() {
+            println!("{}", self.round_name(index));
+            for pairing in round {
+                let winner = pairing.winner(&self.rules).unwrap_or_default();
+                match &pairing.away_team {
+                    Some(away_team) => println!(
+                        "  {:<width$} {:^16} {:<width$}  {}",
+                        pairing.home_team,
+                        pairing.score(),
+                        away_team,
+                        winner,
+                        width = column_width
+                    ),
+                    None => println!(
+                        "  {:<width$} {:^16} {:<width$}  {}",
+                        pairing.home_team,
+                        "bye",
+                        "",
+                        winner,
+                        width = column_width
+                    ),
+                }
+            }
+        }
+
+        if let Some(winner) = self.winner() {
+            println!("Winner: {}", winner);
+        }
+    }
+
+    // One column per round with a box for every match, winners in bold.
+    pub fn to_svg(&self) -> String {
+        let slots = self.rounds.first().map(|round| round.len()).unwrap_or(0);
+        let width = 2 * SVG_MARGIN + self.rounds.len().max(1) * SVG_COLUMN_WIDTH;
+        let height = 2 * SVG_MARGIN + slots.max(1) * SVG_SLOT_HEIGHT;
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" font-family=\"sans-serif\" font-size=\"12\">\n",
+            width, height
+        );
+        svg.push_str("<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n");
+
+        for (round_index, round) in self.rounds.iter().enumerate() {
+            let x = SVG_MARGIN + round_index * SVG_COLUMN_WIDTH;
+            svg.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\" fill=\"#666\">{}</text>\n",
+                x,
+                SVG_MARGIN / 2,
+                xml_escape(&self.round_name(round_index))
+            ));
+
+            // Spread the matches of the round evenly over the height of the first round.
+            let spacing = (height - 2 * SVG_MARGIN) / round.len().max(1);
+            for (index, pairing) in round.iter().enumerate() {
+                let y = SVG_MARGIN + index * spacing + (spacing - SVG_BOX_HEIGHT) / 2;
+                let winner = pairing.winner(&self.rules);
+                svg.push_str(&format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"#999\"/>\n",
+                    x, y, SVG_BOX_WIDTH, SVG_BOX_HEIGHT
+                ));
+
+                let away_team = pairing.away_team.clone().unwrap_or(String::from("bye"));
+                for (line, team) in [&pairing.home_team, &away_team].iter().enumerate() {
+                    let weight = if winner.as_ref() == Some(*team) {
+                        "bold"
+                    } else {
+                        "normal"
+                    };
+                    svg.push_str(&format!(
+                        "<text x=\"{}\" y=\"{}\" font-weight=\"{}\">{}</text>\n",
+                        x + 6,
+                        y + 16 + line * 16,
+                        weight,
+                        xml_escape(team)
+                    ));
+                }
+                if pairing.tie.is_some() {
+                    svg.push_str(&format!(
+                        "<text x=\"{}\" y=\"{}\" text-anchor=\"end\" dominant-baseline=\"middle\">{}</text>\n",
+                        x + SVG_BOX_WIDTH - 6,
+                        y + SVG_BOX_HEIGHT / 2,
+                        xml_escape(&pairing.score())
+                    ));
+                }
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    fn round_name(&self, index: usize) -> String {
+        match self.rounds[index].len() {
+            1 => String::from("Final"),
+            2 => String::from("Semi-finals"),
+            4 => String::from("Quarter-finals"),
+            _ => format!("Round {}", index + 1),
+        }
+    }
+
+    fn seed(&self, team: &str) -> usize {
+        self.entrants
+            .iter()
+            .position(|entrant| entrant == team)
+            .unwrap_or(usize::MAX)
+    }
+}
+
+// A single match cup with extra time and penalties. `entrants` is in seeding order.
+pub fn create_cup(name: &str, entrants: &[String]) -> Cup {
+    Cup {
+        name: name.to_string(),
+        entrants: entrants.to_vec(),
+        rules: TieRules::single_match(),
+        rounds: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standings::matches;
+    use crate::standings::tie;
+
+    fn create_test_cup(count: usize) -> Cup {
+        let entrants: Vec<String> = (1..=count).map(|seed| format!("Team {}", seed)).collect();
+        create_cup("Test Cup", &entrants)
+    }
+
+    fn play(cup: &mut Cup, home_team: &str, away_team: &str, home_score: i32, away_score: i32) {
+        let game = matches::create_match(home_team, away_team, home_score, away_score, 1);
+        cup.add_result(tie::create_tie(game)).unwrap();
+    }
+
+    #[test]
+    fn test_seeded_draw_with_byes() {
+        let mut cup = create_test_cup(6);
+        cup.draw(DrawMode::Seeded).unwrap();
+
+        let round = &cup.rounds[0];
+        assert_eq!(round.len(), 4);
+        assert_eq!(round[0].home_team, "Team 1");
+        assert_eq!(round[0].away_team, None);
+        assert_eq!(round[1].home_team, "Team 2");
+        assert_eq!(round[1].away_team, None);
+        assert_eq!(round[2].home_team, "Team 3");
+        assert_eq!(round[2].away_team, Some(String::from("Team 6")));
+        assert_eq!(round[3].home_team, "Team 4");
+        assert_eq!(round[3].away_team, Some(String::from("Team 5")));
+        assert_eq!(cup.round_name(0), "Quarter-finals");
+    }
+
+    #[test]
+    fn test_random_draw_is_reproducible() {
+        let mut first = create_test_cup(8);
+        let mut second = create_test_cup(8);
+        first.draw(DrawMode::Random(7)).unwrap();
+        second.draw(DrawMode::Random(7)).unwrap();
+
+        assert_eq!(first.rounds, second.rounds);
+        assert_eq!(first.rounds[0].len(), 4);
+        assert!(first.rounds[0]
+            .iter()
+            .all(|pairing| pairing.away_team.is_some()));
+    }
+
+    #[test]
+    fn test_play_to_winner() {
+        let mut cup = create_test_cup(3);
+        cup.draw(DrawMode::Seeded).unwrap();
+        assert_eq!(
+            cup.draw(DrawMode::Seeded),
+            Err("The current round is not finished.")
+        );
+        assert_eq!(
+            cup.add_result(tie::create_tie(matches::create_match(
+                "Team 3", "Team 2", 1, 1, 1
+            ))),
+            Err("The match is not in the current round.")
+        );
+
+        let mut level = tie::create_tie(matches::create_match("Team 2", "Team 3", 1, 1, 1));
+        assert_eq!(
+            cup.add_result(level.clone()),
+            Err("The match needs a winner.")
+        );
+        level.extra_time = Some((0, 0));
+        level.penalties = Some((3, 4));
+        cup.add_result(level).unwrap();
+
+        cup.draw(DrawMode::Seeded).unwrap();
+        assert_eq!(cup.round_name(1), "Final");
+        play(&mut cup, "Team 1", "Team 3", 0, 2);
+
+        assert_eq!(cup.winner(), Some(String::from("Team 3")));
+        assert_eq!(
+            cup.draw(DrawMode::Seeded),
+            Err("The cup is already decided.")
+        );
+        assert!(cup.to_svg().contains("font-weight=\"bold\">Team 3</text>"));
+        assert!(cup.to_svg().contains("1-1 aet (3-4 p)"));
+    }
+}
@@ -0,0 +1,389 @@
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+use crate::standings::calendar;
+use crate::standings::matches::Match;
+
+#[derive(Clone, Debug)]
+pub struct EloConfig {
+    pub k_factor: f64,
+    // Rating points added to the home team when calculating the expected result.
+    pub home_advantage: f64,
+    // Scale the rating change by the margin of victory.
+    pub goal_difference_multiplier: bool,
+    pub initial_rating: f64,
+}
+
+impl Default for EloConfig {
+    fn default() -> EloConfig {
+        EloConfig {
+            k_factor: 20.0,
+            home_advantage: 100.0,
+            goal_difference_multiplier: true,
+            initial_rating: 1500.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RatingChange {
+    pub team: String,
+    pub opponent: String,
+    pub round: u32,
+    // The match date, which tells the meetings of different seasons in the same round apart.
+    pub date: Option<NaiveDate>,
+    pub rating_before: f64,
+    pub rating_after: f64,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Ratings {
+    pub config: EloConfig,
+    // Every rating change in the order the results were entered.
+    pub history: Vec<RatingChange>,
+    current: HashMap<String, f64>,
+}
+
+impl Ratings {
+    pub fn rating(&self, team_name: &str) -> f64 {
+        self.current
+            .get(team_name)
+            .copied()
+            .unwrap_or(self.config.initial_rating)
+    }
+
+    // Probability-like expected score for the home team, draws counting as half a win.
+    pub fn expected_home_score(&self, home_team: &str, away_team: &str) -> f64 {
+        let difference =
+            self.rating(away_team) - (self.rating(home_team) + self.config.home_advantage);
+        1.0 / (1.0 + 10f64.powf(difference / 400.0))
+    }
+
+    // Applies a result unless it is already in the history. The history is read from the
+    // ratings file at startup, so results read again in a later session are not counted twice.
+    pub fn update(&mut self, game: &Match) {
+        if self.has_applied(game) {
+            return;
+        }
+
+        let home_before = self.rating(&game.home_team);
+        let away_before = self.rating(&game.away_team);
+
+        let actual = if game.home_score > game.away_score {
+            1.0
+        } else if game.home_score < game.away_score {
+            0.0
+        } else {
+            0.5
+        };
+        let expected = self.expected_home_score(&game.home_team, &game.away_team);
+        let multiplier = if self.config.goal_difference_multiplier {
+            goal_difference_multiplier((game.home_score - game.away_score).abs())
+        } else {
+            1.0
+        };
+        let change = self.config.k_factor * multiplier * (actual - expected);
+
+        self.record(RatingChange {
+            team: game.home_team.clone(),
+            opponent: game.away_team.clone(),
+            round: game.round,
+            date: game.date(),
+            rating_before: home_before,
+            rating_after: home_before + change,
+        });
+        self.record(RatingChange {
+            team: game.away_team.clone(),
+            opponent: game.home_team.clone(),
+            round: game.round,
+            date: game.date(),
+            rating_before: away_before,
+            rating_after: away_before - change,
+        });
+    }
+
+    // Whether the history has the rating changes of the match. Two teams meet at most once
+    // per round of a season, and the match date tells the seasons apart. The history spans
+    // seasons, so results without a date may be taken for an earlier season's meeting.
+    pub fn has_applied(&self, game: &Match) -> bool {
+        self.history.iter().any(|change| {
+            change.team == game.home_team
+                && change.opponent == game.away_team
+                && change.round == game.round
+                && change.date == game.date()
+        })
+    }
+
+    // Adds a rating change to the history and makes its new rating the current one.
+    pub fn record(&mut self, change: RatingChange) {
+        self.current
+            .insert(change.team.clone(), change.rating_after);
+        self.history.push(change);
+    }
+
+    pub fn team_history(&self, team_name: &str) -> Vec<&RatingChange> {
+        self.history
+            .iter()
+            .filter(|change| change.team == team_name)
+            .collect()
+    }
+
+    // The given teams with their current rating, highest first.
+    pub fn ranking(&self, team_names: &[String]) -> Vec<(String, f64)> {
+        let mut ranking: Vec<(String, f64)> = team_names
+            .iter()
+            .map(|team_name| (team_name.clone(), self.rating(team_name)))
+            .collect();
+        ranking.sort_by(|(_, rating1), (_, rating2)| rating2.partial_cmp(rating1).unwrap());
+        ranking
+    }
+
+    pub fn print(&self, team_names: &[String]) {
+        println!("--------- Elo ratings ------------");
+
+        let column_width: usize = team_names
+            .iter()
+            .map(|team_name| team_name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(4);
+
+        println!(
+            "{:<width$} {:>7}  {:>6}  {:>2}",
+            "Club",
+            "Rating",
+            "Last",
+            "G",
+            width = column_width
+        );
+        for (team_name, rating) in self.ranking(team_names) {
+            let team_history = self.team_history(&team_name);
+            let last_change = match team_history.last() {
+                Some(change) => format!("{:+.1}", change.rating_after - change.rating_before),
+                None => String::from("-"),
+            };
+            println!(
+                "{:<width$} {:>7.1}  {:>6}  {:>2}",
+                team_name,
+                rating,
+                last_change,
+                team_history.len(),
+                width = column_width
+            );
+        }
+    }
+
+    pub fn print_team_history(&self, team_name: &str) {
+        let team_history = self.team_history(team_name);
+        if team_history.is_empty() {
+            println!("No rating history for {}.", team_name);
+            return;
+        }
+
+        println!("Rating history for {}", team_name);
+        for change in team_history {
+            println!(
+                "Round {:>2} vs {:<20} {:>7.1} -> {:>7.1} ({:+.1})",
+                change.round,
+                change.opponent,
+                change.rating_before,
+                change.rating_after,
+                change.rating_after - change.rating_before
+            );
+        }
+    }
+}
+
+// Margin of victory weighting as used by the World Football Elo Ratings.
+fn goal_difference_multiplier(goal_difference: i32) -> f64 {
+    match goal_difference {
+        0 | 1 => 1.0,
+        2 => 1.5,
+        _ => (11.0 + goal_difference as f64) / 8.0,
+    }
+}
+
+// Formats a rating change as "team;opponent;round;before;after" followed by ";date" when the
+// match date is known, the line format of the ratings file.
+pub fn format_rating_change(change: &RatingChange) -> String {
+    let mut line = format!(
+        "{};{};{};{:.2};{:.2}",
+        change.team, change.opponent, change.round, change.rating_before, change.rating_after
+    );
+    if let Some(date) = change.date {
+        line.push_str(&format!(";{}", date));
+    }
+    line
+}
+
+pub fn parse_rating_change(change_str: &str) -> Result<RatingChange, &'static str> {
+    let parts: Vec<&str> = change_str.split(';').collect();
+
+    if parts.len() < 5 || parts.len() > 6 {
+        return Err("Rating line does not contain five or six parts.");
+    }
+
+    let round = parts[2]
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| "Could not parse the round.")?;
+    let rating_before = parts[3]
+        .trim()
+        .parse::<f64>()
+        .map_err(|_| "Could not parse the rating before the match.")?;
+    let rating_after = parts[4]
+        .trim()
+        .parse::<f64>()
+        .map_err(|_| "Could not parse the rating after the match.")?;
+    let date = match parts.get(5).map(|date| date.trim()) {
+        Some(date_str) if !date_str.is_empty() => Some(calendar::parse_date(date_str)?),
+        _ => None,
+    };
+
+    Ok(RatingChange {
+        team: parts[0].trim().to_string(),
+        opponent: parts[1].trim().to_string(),
+        round,
+        date,
+        rating_before,
+        rating_after,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standings::matches;
+
+    #[test]
+    fn test_initial_rating() {
+        let ratings = Ratings::default();
+        assert_eq!(ratings.rating("Foo"), 1500.0);
+    }
+
+    #[test]
+    fn test_update_is_zero_sum() {
+        let mut ratings = Ratings::default();
+        ratings.update(&matches::create_match("Foo", "Bar", 2, 1, 1));
+
+        let foo = ratings.rating("Foo");
+        let bar = ratings.rating("Bar");
+        assert!(foo > 1500.0);
+        assert!(bar < 1500.0);
+        assert!((foo + bar - 3000.0).abs() < 1e-9);
+        assert_eq!(ratings.team_history("Foo").len(), 1);
+        assert_eq!(ratings.history.len(), 2);
+    }
+
+    #[test]
+    fn test_update_skips_applied_match() {
+        let mut saved = Ratings::default();
+        saved.update(&matches::create_match("Foo", "Bar", 2, 1, 1));
+
+        // The history read back from the ratings file, then the same result entered again.
+        let mut ratings = Ratings::default();
+        for change in &saved.history {
+            ratings.record(parse_rating_change(&format_rating_change(change)).unwrap());
+        }
+        let rating_before = ratings.rating("Foo");
+        ratings.update(&matches::create_match("Foo", "Bar", 2, 1, 1));
+        assert_eq!(ratings.rating("Foo"), rating_before);
+        assert_eq!(ratings.history.len(), 2);
+
+        ratings.update(&matches::create_match("Bar", "Foo", 0, 0, 2));
+        assert_eq!(ratings.history.len(), 4);
+    }
+
+    #[test]
+    fn test_update_same_round_next_season() {
+        let dated = |date| Match {
+            kick_off: calendar::parse_kick_off(date).ok(),
+            ..matches::create_match("Foo", "Bar", 2, 1, 1)
+        };
+
+        let mut saved = Ratings::default();
+        saved.update(&dated("2023-04-02 15:00"));
+
+        let mut ratings = Ratings::default();
+        for change in &saved.history {
+            ratings.record(parse_rating_change(&format_rating_change(change)).unwrap());
+        }
+        ratings.update(&dated("2023-04-02 15:00"));
+        assert_eq!(ratings.history.len(), 2);
+
+        // The same pairing in the same round a season later is a new match.
+        let rating_before = ratings.rating("Foo");
+        ratings.update(&dated("2024-03-31 15:00"));
+        assert_eq!(ratings.history.len(), 4);
+        assert!(ratings.rating("Foo") > rating_before);
+    }
+
+    #[test]
+    fn test_home_advantage() {
+        let mut ratings = Ratings::default();
+
+        // A home draw between equal teams is below expectation for the home team.
+        ratings.update(&matches::create_match("Foo", "Bar", 1, 1, 1));
+        assert!(ratings.rating("Foo") < 1500.0);
+        assert!(ratings.rating("Bar") > 1500.0);
+    }
+
+    #[test]
+    fn test_goal_difference_multiplier() {
+        let mut narrow = Ratings::default();
+        let mut wide = Ratings::default();
+        narrow.update(&matches::create_match("Foo", "Bar", 1, 0, 1));
+        wide.update(&matches::create_match("Foo", "Bar", 4, 0, 1));
+
+        assert!(wide.rating("Foo") > narrow.rating("Foo"));
+
+        let mut flat = Ratings {
+            config: EloConfig {
+                goal_difference_multiplier: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        flat.update(&matches::create_match("Foo", "Bar", 4, 0, 1));
+        assert_eq!(flat.rating("Foo"), narrow.rating("Foo"));
+    }
+
+    #[test]
+    fn test_ranking() {
+        let mut ratings = Ratings::default();
+        ratings.update(&matches::create_match("Foo", "Bar", 0, 3, 1));
+
+        let team_names = vec![
+            String::from("Foo"),
+            String::from("Bar"),
+            String::from("Baz"),
+        ];
+        let ranking = ratings.ranking(&team_names);
+        assert_eq!(ranking[0].0, "Bar");
+        assert_eq!(ranking[1], (String::from("Baz"), 1500.0));
+        assert_eq!(ranking[2].0, "Foo");
+    }
+
+    #[test]
+    fn test_parse_rating_change() {
+        let change = parse_rating_change("Foo;Bar;3;1500.00;1512.50").unwrap();
+        assert_eq!(change.team, "Foo");
+        assert_eq!(change.opponent, "Bar");
+        assert_eq!(change.round, 3);
+        assert_eq!(change.rating_after, 1512.5);
+        assert_eq!(format_rating_change(&change), "Foo;Bar;3;1500.00;1512.50");
+
+        let dated = parse_rating_change("Foo;Bar;3;1500.00;1512.50;2024-04-14").unwrap();
+        assert_eq!(dated.date, NaiveDate::from_ymd_opt(2024, 4, 14));
+        assert_eq!(
+            format_rating_change(&dated),
+            "Foo;Bar;3;1500.00;1512.50;2024-04-14"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Could not parse the round.")]
+    fn test_parse_rating_change_panic_incorrect_round() {
+        parse_rating_change("Foo;Bar;x;1500.00;1512.50").unwrap();
+    }
+}
@@ -0,0 +1,299 @@
+use crate::standings::calendar;
+use crate::standings::matches::{Match, ResultType};
+use chrono::NaiveDateTime;
+use std::fmt;
+
+// Why a fixture was called off and when it was meant to be played.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Postponement {
+    pub reason: String,
+    pub original_kick_off: Option<NaiveDateTime>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fixture {
+    pub home_team: String,
+    pub away_team: String,
+    pub round: Option<u32>,
+    // Kick-off in Swedish local time, see `calendar`. The new date once a postponed fixture
+    // has been rescheduled.
+    pub kick_off: Option<NaiveDateTime>,
+    pub postponement: Option<Postponement>,
+}
+
+impl Fixture {
+    pub fn involves(&self, team_name: &str) -> bool {
+        self.home_team == team_name || self.away_team == team_name
+    }
+
+    pub fn is_played_as(&self, game: &Match) -> bool {
+        self.home_team == game.home_team && self.away_team == game.away_team
+    }
+
+    // Postponed and not given a new date yet.
+    pub fn awaits_new_date(&self) -> bool {
+        self.postponement.is_some() && self.kick_off.is_none()
+    }
+}
+
+impl fmt::Display for Fixture {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kick_off = match &self.kick_off {
+            Some(kick_off) => calendar::format_kick_off(kick_off),
+            None => String::from("No date"),
+        };
+        let round = self
+            .round
+            .map(|round| format!("Round {}: ", round))
+            .unwrap_or_default();
+        write!(
+            f,
+            "{} {}{} - {}",
+            kick_off, round, self.home_team, self.away_team
+        )?;
+
+        match &self.postponement {
+            Some(postponement) if self.kick_off.is_some() => {
+                write!(f, " (rescheduled, {}", postponement.reason)?;
+                if let Some(original_kick_off) = &postponement.original_kick_off {
+                    write!(f, ", was {}", calendar::format_kick_off(original_kick_off))?;
+                }
+                write!(f, ")")
+            }
+            Some(postponement) => write!(f, " (postponed, {})", postponement.reason),
+            None => Ok(()),
+        }
+    }
+}
+
+pub fn create_fixture(home_team: &str, away_team: &str) -> Fixture {
+    Fixture {
+        home_team: home_team.to_string(),
+        away_team: away_team.to_string(),
+        round: None,
+        kick_off: None,
+        postponement: None,
+    }
+}
+
+// Parses a fixture line, "team1-team2" optionally followed by the round and the kick-off in
+// the same way as a result, e.g. "AIK-BP;3;2024-04-14 15:00". A postponed fixture also has
+// "postponed:<reason>" and, if it had a date, "original:<kick-off>".
+pub fn parse_fixture(fixture_str: &str) -> Result<Fixture, &'static str> {
+    let parts: Vec<&str> = fixture_str.split(';').collect();
+    let teams: Vec<&str> = parts[0].split('-').collect();
+
+    if teams.len() != 2 {
+        return Err("Invalid fixture format.");
+    }
+
+    let home_team = teams[0].trim();
+    let away_team = teams[1].trim();
+    if home_team.is_empty() || away_team.is_empty() {
+        return Err("Invalid fixture format.");
+    }
+
+    let mut fixture = create_fixture(home_team, away_team);
+    for field in parts[1..].iter().map(|field| field.trim()) {
+        if field.is_empty() {
+            continue;
+        }
+        if let Some(reason) = field.strip_prefix("postponed:") {
+            fixture
+                .postponement
+                .get_or_insert_with(|| create_postponement(""))
+                .reason = reason.trim().to_string();
+        } else if let Some(original) = field.strip_prefix("original:") {
+            let original_kick_off = calendar::parse_kick_off(original)
+                .map_err(|_| "Cannot parse the original kick-off.")?;
+            fixture
+                .postponement
+                .get_or_insert_with(|| create_postponement(""))
+                .original_kick_off = Some(original_kick_off);
+        } else if let Ok(round) = field.parse::<u32>() {
+            fixture.round = Some(round);
+        } else if let Ok(kick_off) = calendar::parse_kick_off(field) {
+            fixture.kick_off = Some(kick_off);
+        } else {
+            return Err("Cannot parse the fixture round or kick-off.");
+        }
+    }
+
+    Ok(fixture)
+}
+
+// Formats the fixture as a line that `parse_fixture` reads back.
+pub fn format_fixture(fixture: &Fixture) -> String {
+    let mut line = format!("{}-{}", fixture.home_team, fixture.away_team);
+    if let Some(round) = fixture.round {
+        line.push_str(&format!(";{}", round));
+    }
+    if let Some(kick_off) = fixture.kick_off {
+        line.push_str(&format!(";{}", kick_off.format("%Y-%m-%d %H:%M")));
+    }
+    if let Some(postponement) = &fixture.postponement {
+        line.push_str(&format!(";postponed:{}", postponement.reason));
+        if let Some(original_kick_off) = postponement.original_kick_off {
+            line.push_str(&format!(
+                ";original:{}",
+                original_kick_off.format("%Y-%m-%d %H:%M")
+            ));
+        }
+    }
+    line
+}
+
+pub fn create_postponement(reason: &str) -> Postponement {
+    Postponement {
+        reason: reason.to_string(),
+        original_kick_off: None,
+    }
+}
+
+// Every team plays every other team once at home and once away.
+pub fn double_round_robin(team_names: &[String]) -> Vec<Fixture> {
+    let mut fixtures = Vec::new();
+    for home_team in team_names {
+        for away_team in team_names {
+            if home_team != away_team {
+                fixtures.push(create_fixture(home_team, away_team));
+            }
+        }
+    }
+    fixtures
+}
+
+// Every team plays every other team once, the team listed first at home.
+pub fn single_round_robin(team_names: &[String]) -> Vec<Fixture> {
+    let mut fixtures = Vec::new();
+    for (index, home_team) in team_names.iter().enumerate() {
+        for away_team in &team_names[index + 1..] {
+            fixtures.push(create_fixture(home_team, away_team));
+        }
+    }
+    fixtures
+}
+
+// The fixtures that have no corresponding result in the match log yet. Each played match
+// accounts for at most one fixture, so teams meeting several times at the same venue work.
+// Abandoned matches leave their fixture open.
+pub fn remaining_fixtures(schedule: &[Fixture], played: &[Match]) -> Vec<Fixture> {
+    let mut unmatched: Vec<&Match> = played
+        .iter()
+        .filter(|game| game.result_type != ResultType::Abandoned)
+        .collect();
+    let mut remaining = Vec::new();
+
+    for fixture in schedule {
+        match unmatched.iter().position(|game| fixture.is_played_as(game)) {
+            Some(index) => {
+                unmatched.swap_remove(index);
+            }
+            None => remaining.push(fixture.clone()),
+        }
+    }
+
+    remaining
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standings::matches;
+
+    #[test]
+    fn test_parse_fixture() {
+        let fixture = parse_fixture("AIK-BP;3;2024-04-14 15:00").unwrap();
+
+        assert_eq!(fixture.home_team, "AIK");
+        assert_eq!(fixture.away_team, "BP");
+        assert_eq!(fixture.round, Some(3));
+        assert_eq!(
+            fixture.kick_off,
+            Some(calendar::parse_kick_off("2024-04-14 15:00").unwrap())
+        );
+
+        let without_details = parse_fixture("AIK-BP").unwrap();
+        assert_eq!(without_details.round, None);
+        assert_eq!(without_details.kick_off, None);
+    }
+
+    #[test]
+    fn test_postponed_fixture() {
+        let line = "AIK-BP;3;2024-05-02 19:00;postponed:Frozen pitch;original:2024-04-07 15:00";
+        let fixture = parse_fixture(line).unwrap();
+
+        assert_eq!(
+            fixture.kick_off,
+            calendar::parse_kick_off("2024-05-02 19:00").ok()
+        );
+        let postponement = fixture.postponement.as_ref().unwrap();
+        assert_eq!(postponement.reason, "Frozen pitch");
+        assert_eq!(
+            postponement.original_kick_off,
+            calendar::parse_kick_off("2024-04-07 15:00").ok()
+        );
+        assert!(!fixture.awaits_new_date());
+        assert_eq!(format_fixture(&fixture), line);
+        assert_eq!(
+            fixture.to_string(),
+            "2024-05-02 19:00 +02:00 Round 3: AIK - BP (rescheduled, Frozen pitch, was 2024-04-07 15:00 +02:00)"
+        );
+
+        let waiting = parse_fixture("AIK-BP;postponed:Snow").unwrap();
+        assert!(waiting.awaits_new_date());
+        assert_eq!(waiting.to_string(), "No date AIK - BP (postponed, Snow)");
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid fixture format.")]
+    fn test_parse_fixture_panic_missing_team() {
+        parse_fixture("AIK;3").unwrap();
+    }
+
+    #[test]
+    fn test_double_round_robin() {
+        let team_names = vec![
+            String::from("FC Test"),
+            String::from("Foo"),
+            String::from("Bar"),
+        ];
+
+        let fixtures = double_round_robin(&team_names);
+        assert_eq!(fixtures.len(), 6);
+        assert!(fixtures.contains(&create_fixture("Foo", "Bar")));
+        assert!(fixtures.contains(&create_fixture("Bar", "Foo")));
+    }
+
+    #[test]
+    fn test_single_round_robin() {
+        let team_names = vec![
+            String::from("FC Test"),
+            String::from("Foo"),
+            String::from("Bar"),
+        ];
+
+        let fixtures = single_round_robin(&team_names);
+        assert_eq!(fixtures.len(), 3);
+        assert!(fixtures.contains(&create_fixture("Foo", "Bar")));
+        assert!(!fixtures.contains(&create_fixture("Bar", "Foo")));
+    }
+
+    #[test]
+    fn test_remaining_fixtures() {
+        let team_names = vec![
+            String::from("FC Test"),
+            String::from("Foo"),
+            String::from("Bar"),
+        ];
+        let played = vec![
+            matches::create_match("Foo", "Bar", 1, 0, 1),
+            matches::create_match("Bar", "FC Test", 1, 1, 2),
+        ];
+
+        let remaining = remaining_fixtures(&double_round_robin(&team_names), &played);
+        assert_eq!(remaining.len(), 4);
+        assert!(!remaining.contains(&create_fixture("Foo", "Bar")));
+        assert!(remaining.contains(&create_fixture("Bar", "Foo")));
+    }
+}
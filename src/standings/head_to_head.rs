@@ -0,0 +1,119 @@
+use crate::standings::matches::{Match, Venue};
+
+// All meetings between two teams, with the record seen from the first team.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HeadToHead {
+    pub team1: String,
+    pub team2: String,
+    // Season label and match, oldest season first.
+    pub meetings: Vec<(String, Match)>,
+    pub wins: usize,
+    pub draws: usize,
+    pub defeats: usize,
+    pub goals_for: i32,
+    pub goals_against: i32,
+}
+
+impl HeadToHead {
+    pub fn print(&self) {
+        println!("{} vs {}", self.team1, self.team2);
+
+        if self.meetings.is_empty() {
+            println!("The teams have not met.");
+            return;
+        }
+
+        let season_width = self
+            .meetings
+            .iter()
+            .map(|(season, _)| season.chars().count())
+            .max()
+            .unwrap_or(0);
+        for (season, game) in &self.meetings {
+            let venue = if game.home_team == self.team1 {
+                Venue::Home
+            } else {
+                Venue::Away
+            };
+            println!(
+                "  {:<width$} {} ({} {})",
+                season,
+                game,
+                self.team1,
+                venue.name(),
+                width = season_width
+            );
+        }
+
+        println!(
+            "{}: {} W, {} D, {} L, goals {}-{}",
+            self.team1, self.wins, self.draws, self.defeats, self.goals_for, self.goals_against
+        );
+    }
+}
+
+// Collects the meetings of two teams from the given seasons, each a label and its match log.
+pub fn head_to_head(team1: &str, team2: &str, seasons: &[(&str, &[Match])]) -> HeadToHead {
+    let mut record = HeadToHead {
+        team1: team1.to_string(),
+        team2: team2.to_string(),
+        ..Default::default()
+    };
+
+    for (season, games) in seasons {
+        let mut meetings: Vec<&Match> = games
+            .iter()
+            .filter(|game| game.involves(team1) && game.involves(team2))
+            .filter(|game| game.counts_in_table())
+            .collect();
+        meetings.sort_by_key(|game| (game.round, game.kick_off));
+
+        for game in meetings {
+            let Some((scored, conceded)) = game.score_for(team1) else {
+                continue;
+            };
+            if scored > conceded {
+                record.wins += 1;
+            } else if scored < conceded {
+                record.defeats += 1;
+            } else {
+                record.draws += 1;
+            }
+            record.goals_for += scored;
+            record.goals_against += conceded;
+            record.meetings.push((season.to_string(), game.clone()));
+        }
+    }
+
+    record
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standings::matches;
+
+    #[test]
+    fn test_head_to_head() {
+        let last_season = vec![
+            matches::create_match("Foo", "Bar", 2, 2, 4),
+            matches::create_match("Bar", "Baz", 1, 0, 5),
+        ];
+        let this_season = vec![
+            matches::create_match("Bar", "Foo", 0, 3, 7),
+            matches::create_match("Foo", "Bar", 0, 1, 2),
+        ];
+
+        let record = head_to_head(
+            "Foo",
+            "Bar",
+            &[("2023", &last_season), ("2024", &this_season)],
+        );
+
+        assert_eq!(record.meetings.len(), 3);
+        assert_eq!(record.meetings[0].0, "2023");
+        assert_eq!(record.meetings[1].1.round, 2);
+        assert_eq!((record.wins, record.draws, record.defeats), (1, 1, 1));
+        assert_eq!((record.goals_for, record.goals_against), (5, 3));
+    }
+}
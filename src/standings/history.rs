@@ -0,0 +1,206 @@
+use crate::standings::tables::Table;
+
+const SVG_LABEL_WIDTH: usize = 160;
+const SVG_ROUND_WIDTH: usize = 40;
+const SVG_ROW_HEIGHT: usize = 24;
+const SVG_MARGIN: usize = 30;
+
+// League position of every team after each round of the match log.
+pub struct PositionHistory {
+    pub rounds: Vec<u32>,
+    // Team name and its position after each of `rounds`, in final table order.
+    pub positions: Vec<(String, Vec<usize>)>,
+}
+
+impl PositionHistory {
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("Team");
+        for round in &self.rounds {
+            csv.push_str(&format!(",{}", round));
+        }
+        csv.push('\n');
+
+        for (team_name, positions) in &self.positions {
+            csv.push_str(&csv_field(team_name));
+            for position in positions {
+                csv.push_str(&format!(",{}", position));
+            }
+            csv.push('\n');
+        }
+
+        csv
+    }
+
+    // Bump chart with rounds on the x-axis and one line per team, first place at the top.
+    pub fn to_svg(&self) -> String {
+        let team_count = self.positions.len();
+        let width = 2 * SVG_LABEL_WIDTH + self.rounds.len().saturating_sub(1) * SVG_ROUND_WIDTH;
+        let height = 2 * SVG_MARGIN + team_count.saturating_sub(1) * SVG_ROW_HEIGHT;
+
+        let x = |round_index: usize| SVG_LABEL_WIDTH + round_index * SVG_ROUND_WIDTH;
+        let y = |position: usize| SVG_MARGIN + (position - 1) * SVG_ROW_HEIGHT;
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" font-family=\"sans-serif\" font-size=\"12\">\n",
+            width,
+            height + SVG_MARGIN
+        );
+        svg.push_str("<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n");
+
+        for (i, round) in self.rounds.iter().enumerate() {
+            svg.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" fill=\"#666\">{}</text>\n",
+                x(i),
+                height + SVG_MARGIN / 2,
+                round
+            ));
+        }
+
+        for (team_index, (team_name, positions)) in self.positions.iter().enumerate() {
+            if positions.is_empty() {
+                continue;
+            }
+
+            let colour = format!("hsl({},65%,45%)", team_index * 360 / team_count.max(1));
+            let points: Vec<String> = positions
+                .iter()
+                .enumerate()
+                .map(|(i, position)| format!("{},{}", x(i), y(*position)))
+                .collect();
+            let name = xml_escape(team_name);
+            let first = positions[0];
+            let last = positions[positions.len() - 1];
+
+            svg.push_str(&format!(
+                "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"3\"/>\n",
+                points.join(" "),
+                colour
+            ));
+            for (i, position) in positions.iter().enumerate() {
+                svg.push_str(&format!(
+                    "<circle cx=\"{}\" cy=\"{}\" r=\"4\" fill=\"{}\"/>\n",
+                    x(i),
+                    y(*position),
+                    colour
+                ));
+            }
+            svg.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\" text-anchor=\"end\" dominant-baseline=\"middle\">{}. {}</text>\n",
+                x(0) - 10,
+                y(first),
+                first,
+                name
+            ));
+            svg.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\" dominant-baseline=\"middle\">{}. {}</text>\n",
+                x(positions.len() - 1) + 10,
+                y(last),
+                last,
+                name
+            ));
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+pub fn position_history(table: &Table) -> PositionHistory {
+    let rounds: Vec<u32> = (1..=table.last_round()).collect();
+    let standings: Vec<Table> = rounds
+        .iter()
+        .map(|round| table.standings_after_round(*round))
+        .collect();
+
+    let final_order: Vec<String> = match standings.last() {
+        Some(last) => last.teams.iter().map(|team| team.name.clone()).collect(),
+        None => table.teams.iter().map(|team| team.name.clone()).collect(),
+    };
+
+    let positions = final_order
+        .into_iter()
+        .map(|team_name| {
+            let team_positions: Vec<usize> = standings
+                .iter()
+                .filter_map(|round_table| round_table.position(&team_name))
+                .collect();
+            (team_name, team_positions)
+        })
+        .collect();
+
+    PositionHistory { rounds, positions }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains(',') || value.contains('"') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standings::matches;
+    use crate::standings::tables;
+
+    fn create_test_table() -> Table {
+        let team_names = vec![
+            String::from("FC Test"),
+            String::from("Foo"),
+            String::from("Bar & Baz"),
+        ];
+
+        let mut test_table = tables::create_table(&team_names);
+        test_table.add_match(matches::create_match("FC Test", "Foo", 2, 0, 1));
+        test_table.add_match(matches::create_match("Foo", "Bar & Baz", 3, 0, 2));
+        test_table.add_match(matches::create_match("Bar & Baz", "FC Test", 5, 0, 3));
+        test_table
+    }
+
+    #[test]
+    fn test_position_history() {
+        let history = position_history(&create_test_table());
+
+        assert_eq!(history.rounds, vec![1, 2, 3]);
+        assert_eq!(history.positions[0].0, "Bar & Baz");
+        assert_eq!(history.positions[0].1, vec![2, 3, 1]);
+        assert_eq!(history.positions[1].0, "Foo");
+        assert_eq!(history.positions[1].1, vec![3, 2, 2]);
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let csv = position_history(&create_test_table()).to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], "Team,1,2,3");
+        assert_eq!(lines[1], "Bar & Baz,2,3,1");
+        assert_eq!(lines.len(), 4);
+    }
+
+    #[test]
+    fn test_to_svg() {
+        let svg = position_history(&create_test_table()).to_svg();
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<polyline").count(), 3);
+        assert!(svg.contains("Bar &amp; Baz"));
+    }
+
+    #[test]
+    fn test_csv_field() {
+        assert_eq!(csv_field("AIK"), "AIK");
+        assert_eq!(csv_field("A, B"), "\"A, B\"");
+    }
+}
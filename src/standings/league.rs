@@ -0,0 +1,157 @@
+use crate::standings::competition::Competition;
+use crate::standings::squads::Squads;
+use crate::standings::tables::Table;
+
+#[derive(Clone, Debug)]
+pub struct Division {
+    pub competition: Competition,
+    pub table: Table,
+}
+
+// The divisions of a league pyramid loaded together, top division first. Menu commands work
+// on the selected division.
+#[derive(Clone, Debug, Default)]
+pub struct League {
+    pub divisions: Vec<Division>,
+    pub current: usize,
+    // The players of every team in the league.
+    pub squads: Squads,
+}
+
+impl League {
+    pub fn add_division(&mut self, competition: Competition, mut table: Table) {
+        table.name = competition.name.clone();
+        table.zones = competition.zones.clone();
+        self.divisions.push(Division { competition, table });
+    }
+
+    pub fn division(&self) -> &Division {
+        &self.divisions[self.current]
+    }
+
+    pub fn table(&self) -> &Table {
+        &self.divisions[self.current].table
+    }
+
+    pub fn table_mut(&mut self) -> &mut Table {
+        &mut self.divisions[self.current].table
+    }
+
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.divisions
+            .iter()
+            .position(|division| division.competition.name.to_lowercase() == name.to_lowercase())
+    }
+
+    pub fn select(&mut self, name: &str) -> Result<(), &'static str> {
+        self.current = self.find(name).ok_or("Unknown division.")?;
+        Ok(())
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.divisions
+            .iter()
+            .map(|division| division.competition.name.as_str())
+            .collect()
+    }
+
+    // A table with the teams of every division, for matches between divisions such as
+    // playoffs and cup ties.
+    pub fn registry(&self) -> Table {
+        let teams = self
+            .divisions
+            .iter()
+            .flat_map(|division| division.table.teams.iter().cloned())
+            .collect();
+        Table {
+            name: String::from("All divisions"),
+            teams,
+            ..Default::default()
+        }
+    }
+
+    // The division both teams of a match play in.
+    pub fn division_for_match(
+        &self,
+        home_team: &str,
+        away_team: &str,
+    ) -> Result<usize, &'static str> {
+        let home = self
+            .division_of(home_team)
+            .ok_or("Home team does not exist.")?;
+        let away = self
+            .division_of(away_team)
+            .ok_or("Away team does not exist")?;
+
+        if home != away {
+            return Err("The teams play in different divisions.");
+        }
+        Ok(home)
+    }
+
+    fn division_of(&self, team_name: &str) -> Option<usize> {
+        self.divisions.iter().position(|division| {
+            division
+                .table
+                .teams
+                .iter()
+                .any(|team| team.name == team_name)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standings::competition;
+    use crate::standings::tables;
+
+    fn create_test_league() -> League {
+        let mut league = League::default();
+        league.add_division(
+            competition::parse_competition("Allsvenskan;1;3;1;2;Superettan").unwrap(),
+            tables::create_table(&[String::from("Foo"), String::from("Bar")]),
+        );
+        league.add_division(
+            competition::parse_competition("Superettan;1;0;2;2").unwrap(),
+            tables::create_table(&[String::from("Baz"), String::from("Qux")]),
+        );
+        league
+    }
+
+    #[test]
+    fn test_select() {
+        let mut league = create_test_league();
+
+        assert_eq!(league.table().name, "Allsvenskan");
+        league.select("superettan").unwrap();
+        assert_eq!(league.current, 1);
+        assert_eq!(league.division().competition.name, "Superettan");
+        assert!(league.select("Ettan").is_err());
+        assert_eq!(league.current, 1);
+    }
+
+    #[test]
+    fn test_registry() {
+        let registry = create_test_league().registry();
+
+        assert_eq!(registry.teams.len(), 4);
+        assert!(registry.position("Qux").is_some());
+    }
+
+    #[test]
+    fn test_division_for_match() {
+        let league = create_test_league();
+
+        assert_eq!(league.division_for_match("Baz", "Qux"), Ok(1));
+        assert_eq!(league.division_for_match("Foo", "Bar"), Ok(0));
+        assert_eq!(
+            league.division_for_match("Foo", "Qux"),
+            Err("The teams play in different divisions.")
+        );
+        assert_eq!(
+            league.division_for_match("Nope", "Qux"),
+            Err("Home team does not exist.")
+        );
+    }
+}
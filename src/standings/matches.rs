@@ -0,0 +1,395 @@
+use crate::standings::calendar;
+use crate::standings::cards;
+use crate::standings::cards::Card;
+use crate::standings::scorers;
+use crate::standings::scorers::Goal;
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, Utc, Weekday};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Venue {
+    Home,
+    Away,
+}
+
+impl Venue {
+    pub fn from_string(s: &str) -> Option<Venue> {
+        match s.trim().to_lowercase().as_str() {
+            "home" | "h" => Some(Venue::Home),
+            "away" | "a" => Some(Venue::Away),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Venue::Home => "home",
+            Venue::Away => "away",
+        }
+    }
+}
+
+// How a result came about. Abandoned and annulled matches stay in the match log but count
+// nowhere else.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ResultType {
+    #[default]
+    Normal,
+    // The score includes the goals of extra time.
+    AfterExtraTime,
+    // Level after extra time and decided by the shootout, home and away penalties. The table
+    // counts the match as a draw.
+    Penalties(i32, i32),
+    // Awarded by the association. Counts in the table, but not in statistics or ratings.
+    Walkover,
+    // Stopped before full time. The fixture is still to be played.
+    Abandoned,
+    // Struck from the record. The fixture is not replayed.
+    Annulled,
+}
+
+impl ResultType {
+    // The qualifier written after the score, e.g. "aet" or "(5-4 p)".
+    pub fn qualifier(&self) -> String {
+        match self {
+            ResultType::Normal => String::new(),
+            ResultType::AfterExtraTime => String::from("aet"),
+            ResultType::Penalties(home_penalties, away_penalties) => {
+                format!("({}-{} p)", home_penalties, away_penalties)
+            }
+            ResultType::Walkover => String::from("w.o."),
+            ResultType::Abandoned => String::from("abandoned"),
+            ResultType::Annulled => String::from("annulled"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Match {
+    pub home_team: String,
+    pub away_team: String,
+    pub home_score: i32,
+    pub away_score: i32,
+    pub round: u32,
+    // Kick-off in Swedish local time, see `calendar`.
+    pub kick_off: Option<NaiveDateTime>,
+    pub result_type: ResultType,
+    // Goal events, when they have been entered. Their number matches the score.
+    pub goals: Vec<Goal>,
+    pub cards: Vec<Card>,
+}
+
+impl Match {
+    // Whether the result gives points and goals in the table.
+    pub fn counts_in_table(&self) -> bool {
+        !matches!(
+            self.result_type,
+            ResultType::Abandoned | ResultType::Annulled
+        )
+    }
+
+    // Whether the score was played out on the pitch, for statistics, ratings and models.
+    pub fn counts_in_statistics(&self) -> bool {
+        self.counts_in_table() && self.result_type != ResultType::Walkover
+    }
+
+    // The score with its qualifier, e.g. "3-3 (5-4 p)".
+    pub fn score(&self) -> String {
+        let score = format!("{}-{}", self.home_score, self.away_score);
+        match self.result_type {
+            ResultType::Normal => score,
+            result_type => format!("{} {}", score, result_type.qualifier()),
+        }
+    }
+
+    pub fn involves(&self, team_name: &str) -> bool {
+        self.home_team == team_name || self.away_team == team_name
+    }
+
+    // The team playing at the given venue together with its goals scored and conceded.
+    pub fn side(&self, venue: Venue) -> (&str, i32, i32) {
+        match venue {
+            Venue::Home => (&self.home_team, self.home_score, self.away_score),
+            Venue::Away => (&self.away_team, self.away_score, self.home_score),
+        }
+    }
+
+    // Goals scored and conceded by the given team, if it took part in the match.
+    pub fn score_for(&self, team_name: &str) -> Option<(i32, i32)> {
+        if self.home_team == team_name {
+            Some((self.home_score, self.away_score))
+        } else if self.away_team == team_name {
+            Some((self.away_score, self.home_score))
+        } else {
+            None
+        }
+    }
+
+    pub fn date(&self) -> Option<NaiveDate> {
+        self.kick_off.map(|kick_off| kick_off.date())
+    }
+
+    pub fn weekday(&self) -> Option<Weekday> {
+        self.kick_off.map(|kick_off| kick_off.weekday())
+    }
+
+    pub fn kick_off_utc(&self) -> Option<DateTime<Utc>> {
+        self.kick_off.as_ref().map(calendar::stockholm_to_utc)
+    }
+}
+
+impl fmt::Display for Match {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kick_off = match &self.kick_off {
+            Some(kick_off) => calendar::format_kick_off(kick_off),
+            None => String::from("No date"),
+        };
+
+        write!(
+            f,
+            "{} Round {}: {} - {} {}",
+            kick_off,
+            self.round,
+            self.home_team,
+            self.away_team,
+            self.score()
+        )
+    }
+}
+
+pub fn create_match(
+    home_team: &str,
+    away_team: &str,
+    home_score: i32,
+    away_score: i32,
+    round: u32,
+) -> Match {
+    Match {
+        home_team: home_team.to_string(),
+        away_team: away_team.to_string(),
+        home_score,
+        away_score,
+        round,
+        kick_off: None,
+        result_type: ResultType::Normal,
+        goals: Vec::new(),
+        cards: Vec::new(),
+    }
+}
+
+// Splits the score field of a result line into the score and the qualifier after it, e.g.
+// "3-3 (5-4 p)" into "3-3" and "(5-4 p)". Spaces around the dash belong to the score.
+pub fn split_score(score_str: &str) -> (&str, &str) {
+    let score_str = score_str.trim();
+    let Some((_, away)) = score_str.split_once('-') else {
+        return (score_str, "");
+    };
+    let away = away.trim_start();
+    let away_digits = away
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(away.len());
+    let end = score_str.len() - away.len() + away_digits;
+    (&score_str[..end], score_str[end..].trim())
+}
+
+// Parses the qualifier after the score of a result line: "aet", "(5-4 p)" optionally after
+// "aet", "w.o.", "abandoned" or "annulled". An empty qualifier is a normal result.
+pub fn parse_result_type(qualifier_str: &str) -> Result<ResultType, &'static str> {
+    let qualifier = qualifier_str.trim().to_lowercase();
+    let qualifier = qualifier
+        .strip_prefix("aet")
+        .filter(|rest| rest.trim_start().starts_with('('))
+        .unwrap_or(&qualifier)
+        .trim();
+
+    match qualifier {
+        "" => Ok(ResultType::Normal),
+        "aet" => Ok(ResultType::AfterExtraTime),
+        "w.o." | "wo" => Ok(ResultType::Walkover),
+        "abandoned" => Ok(ResultType::Abandoned),
+        "annulled" => Ok(ResultType::Annulled),
+        _ => {
+            let penalties = qualifier
+                .strip_prefix('(')
+                .and_then(|rest| rest.strip_suffix("p)"))
+                .ok_or("Unknown result qualifier.")?;
+            let (home_penalties, away_penalties) = penalties
+                .trim()
+                .split_once('-')
+                .ok_or("Invalid penalty score format.")?;
+            let home_penalties = home_penalties
+                .trim()
+                .parse::<i32>()
+                .map_err(|_| "Cannot parse the home team penalties.")?;
+            let away_penalties = away_penalties
+                .trim()
+                .parse::<i32>()
+                .map_err(|_| "Cannot parse the away team penalties.")?;
+            if home_penalties < 0 || away_penalties < 0 {
+                return Err("The penalties cannot be negative.");
+            }
+            if home_penalties == away_penalties {
+                return Err("A shootout needs a winner.");
+            }
+            Ok(ResultType::Penalties(home_penalties, away_penalties))
+        }
+    }
+}
+
+// Formats the match as a result line that `parse_result` reads back, e.g.
+// "AIK-BP;1-0;3;2024-04-14 15:00".
+pub fn format_result(game: &Match) -> String {
+    let mut result = format!(
+        "{}-{};{};{}",
+        game.home_team,
+        game.away_team,
+        game.score(),
+        game.round
+    );
+    if let Some(kick_off) = game.kick_off {
+        result.push_str(&format!(";{}", kick_off.format("%Y-%m-%d %H:%M")));
+    }
+    if !game.goals.is_empty() {
+        result.push_str(&format!(";{}", scorers::format_goals(&game.goals)));
+    }
+    if !game.cards.is_empty() {
+        result.push_str(&format!(";{}", cards::format_cards(&game.cards)));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_match() {
+        let game = create_match("Foo", "Bar", 2, 1, 3);
+
+        assert_eq!(game.home_team, String::from("Foo"));
+        assert_eq!(game.away_team, String::from("Bar"));
+        assert_eq!(game.home_score, 2);
+        assert_eq!(game.away_score, 1);
+        assert_eq!(game.round, 3);
+    }
+
+    #[test]
+    fn test_kick_off() {
+        let game = Match {
+            kick_off: Some(calendar::parse_kick_off("2024-04-06 15:00").unwrap()),
+            ..create_match("Foo", "Bar", 2, 1, 3)
+        };
+
+        assert_eq!(game.date(), NaiveDate::from_ymd_opt(2024, 4, 6));
+        assert_eq!(game.weekday(), Some(Weekday::Sat));
+        assert_eq!(
+            game.kick_off_utc().unwrap().to_rfc3339(),
+            "2024-04-06T13:00:00+00:00"
+        );
+        assert_eq!(
+            game.to_string(),
+            "2024-04-06 15:00 +02:00 Round 3: Foo - Bar 2-1"
+        );
+    }
+
+    #[test]
+    fn test_side() {
+        let game = create_match("Foo", "Bar", 2, 1, 3);
+
+        assert_eq!(game.side(Venue::Home), ("Foo", 2, 1));
+        assert_eq!(game.side(Venue::Away), ("Bar", 1, 2));
+        assert_eq!(Venue::from_string("Away"), Some(Venue::Away));
+        assert_eq!(Venue::from_string("neutral"), None);
+    }
+
+    #[test]
+    fn test_score_for() {
+        let game = create_match("Foo", "Bar", 2, 1, 3);
+
+        assert_eq!(game.score_for("Foo"), Some((2, 1)));
+        assert_eq!(game.score_for("Bar"), Some((1, 2)));
+        assert_eq!(game.score_for("FC Test"), None);
+    }
+
+    #[test]
+    fn test_format_result() {
+        let game = create_match("Foo", "Bar", 2, 1, 3);
+        assert_eq!(format_result(&game), "Foo-Bar;2-1;3");
+
+        let dated_game = Match {
+            kick_off: Some(calendar::parse_kick_off("2024-04-06 15:00").unwrap()),
+            ..game
+        };
+        assert_eq!(format_result(&dated_game), "Foo-Bar;2-1;3;2024-04-06 15:00");
+
+        let shootout = Match {
+            result_type: ResultType::Penalties(5, 4),
+            ..create_match("Foo", "Bar", 1, 1, 3)
+        };
+        assert_eq!(format_result(&shootout), "Foo-Bar;1-1 (5-4 p);3");
+    }
+
+    #[test]
+    fn test_split_score() {
+        assert_eq!(split_score("1-0"), ("1-0", ""));
+        assert_eq!(split_score("1 - 0"), ("1 - 0", ""));
+        assert_eq!(split_score("3 - 3 (5-4 p)"), ("3 - 3", "(5-4 p)"));
+        assert_eq!(split_score("3-0 w.o."), ("3-0", "w.o."));
+    }
+
+    #[test]
+    fn test_parse_result_type() {
+        assert_eq!(parse_result_type(""), Ok(ResultType::Normal));
+        assert_eq!(parse_result_type("AET"), Ok(ResultType::AfterExtraTime));
+        assert_eq!(
+            parse_result_type("(5-4 p)"),
+            Ok(ResultType::Penalties(5, 4))
+        );
+        assert_eq!(
+            parse_result_type("aet (3-5 p)"),
+            Ok(ResultType::Penalties(3, 5))
+        );
+        assert_eq!(parse_result_type("w.o."), Ok(ResultType::Walkover));
+        assert_eq!(parse_result_type("annulled"), Ok(ResultType::Annulled));
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown result qualifier.")]
+    fn test_parse_result_type_panic_unknown() {
+        parse_result_type("golden goal").unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "A shootout needs a winner.")]
+    fn test_parse_result_type_panic_level_penalties() {
+        parse_result_type("(5-5 p)").unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "The penalties cannot be negative.")]
+    fn test_parse_result_type_panic_negative_penalties() {
+        parse_result_type("(5--4 p)").unwrap();
+    }
+
+    #[test]
+    fn test_counts() {
+        let mut game = create_match("Foo", "Bar", 3, 0, 3);
+        assert!(game.counts_in_statistics());
+
+        game.result_type = ResultType::Walkover;
+        assert!(game.counts_in_table());
+        assert!(!game.counts_in_statistics());
+
+        game.result_type = ResultType::Abandoned;
+        assert!(!game.counts_in_table());
+    }
+
+    #[test]
+    fn test_involves() {
+        let game = create_match("Foo", "Bar", 2, 1, 3);
+
+        assert!(game.involves("Foo"));
+        assert!(game.involves("Bar"));
+        assert!(!game.involves("FC Test"));
+    }
+}
@@ -0,0 +1,437 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+
+use crate::standings::matches::Match;
+use crate::standings::random::Rng;
+use crate::standings::simulation::MatchModel;
+
+// Scores above this number of goals per team are ignored when predicting.
+pub const MAX_GOALS: usize = 10;
+// Matches without a kick-off are assumed to be this many days apart per round.
+const DAYS_PER_ROUND: f64 = 7.0;
+// Weight of the average-team pseudo matches every team starts from, so that teams with few
+// matches get moderate strengths.
+const PRIOR_WEIGHT: f64 = 3.0;
+
+pub struct FitConfig {
+    // Exponential time decay per day, 0.0 weighs all matches equally.
+    pub decay_per_day: f64,
+    pub iterations: usize,
+}
+
+impl Default for FitConfig {
+    fn default() -> FitConfig {
+        FitConfig {
+            decay_per_day: 0.0019,
+            iterations: 50,
+        }
+    }
+}
+
+// Dixon-Coles model: Poisson goals from attack and defence strengths with a home advantage and
+// a correction (rho) for the frequency of low scores.
+#[derive(Clone, Debug)]
+pub struct DixonColesModel {
+    // Attack and defence relative to an average team, a high defence value conceding more.
+    strengths: HashMap<String, (f64, f64)>,
+    pub average_goals: f64,
+    pub home_advantage: f64,
+    pub rho: f64,
+}
+
+impl DixonColesModel {
+    pub fn fit(team_names: &[String], matches: &[Match], config: &FitConfig) -> DixonColesModel {
+        let weights = match_weights(matches, config.decay_per_day);
+        let total_weight: f64 = weights.iter().sum();
+
+        let mut model = DixonColesModel {
+            strengths: team_names
+                .iter()
+                .map(|team_name| (team_name.clone(), (1.0, 1.0)))
+                .collect(),
+            average_goals: 1.35,
+            home_advantage: 1.0,
+            rho: 0.0,
+        };
+        if total_weight == 0.0 {
+            return model;
+        }
+
+        let home_goals: f64 = matches
+            .iter()
+            .zip(&weights)
+            .map(|(game, weight)| weight * game.home_score as f64)
+            .sum();
+        let away_goals: f64 = matches
+            .iter()
+            .zip(&weights)
+            .map(|(game, weight)| weight * game.away_score as f64)
+            .sum();
+        model.average_goals = ((home_goals + away_goals) / (2.0 * total_weight)).max(0.1);
+
+        // Alternate between the maximum likelihood estimates of each parameter group given the
+        // others until they settle.
+        for _ in 0..config.iterations {
+            let mut attack_goals: HashMap<&str, f64> = HashMap::new();
+            let mut attack_expected: HashMap<&str, f64> = HashMap::new();
+            let mut defence_goals: HashMap<&str, f64> = HashMap::new();
+            let mut defence_expected: HashMap<&str, f64> = HashMap::new();
+
+            for (game, weight) in matches.iter().zip(&weights) {
+                let (home_attack, home_defence) = model.strength(&game.home_team);
+                let (away_attack, away_defence) = model.strength(&game.away_team);
+                let home_base = model.average_goals * model.home_advantage;
+                let away_base = model.average_goals;
+
+                *attack_goals.entry(&game.home_team).or_default() +=
+                    weight * game.home_score as f64;
+                *attack_expected.entry(&game.home_team).or_default() +=
+                    weight * home_base * away_defence;
+                *attack_goals.entry(&game.away_team).or_default() +=
+                    weight * game.away_score as f64;
+                *attack_expected.entry(&game.away_team).or_default() +=
+                    weight * away_base * home_defence;
+
+                *defence_goals.entry(&game.home_team).or_default() +=
+                    weight * game.away_score as f64;
+                *defence_expected.entry(&game.home_team).or_default() +=
+                    weight * away_base * away_attack;
+                *defence_goals.entry(&game.away_team).or_default() +=
+                    weight * game.home_score as f64;
+                *defence_expected.entry(&game.away_team).or_default() +=
+                    weight * home_base * home_attack;
+            }
+
+            let mut strengths = model.strengths.clone();
+            for (team_name, (attack, defence)) in strengths.iter_mut() {
+                let team = team_name.as_str();
+                *attack = (attack_goals.get(team).unwrap_or(&0.0)
+                    + PRIOR_WEIGHT * model.average_goals)
+                    / (attack_expected.get(team).unwrap_or(&0.0)
+                        + PRIOR_WEIGHT * model.average_goals);
+                *defence = (defence_goals.get(team).unwrap_or(&0.0)
+                    + PRIOR_WEIGHT * model.average_goals)
+                    / (defence_expected.get(team).unwrap_or(&0.0)
+                        + PRIOR_WEIGHT * model.average_goals);
+            }
+
+            // Keep the average attack at 1.0 so the parameters stay identifiable.
+            let mean_attack = strengths.values().map(|(attack, _)| attack).sum::<f64>()
+                / strengths.len().max(1) as f64;
+            strengths
+                .values_mut()
+                .for_each(|(attack, _)| *attack /= mean_attack);
+            model.strengths = strengths;
+
+            let expected_home: f64 = matches
+                .iter()
+                .zip(&weights)
+                .map(|(game, weight)| {
+                    weight
+                        * model.average_goals
+                        * model.strength(&game.home_team).0
+                        * model.strength(&game.away_team).1
+                })
+                .sum();
+            if expected_home > 0.0 {
+                model.home_advantage = home_goals / expected_home;
+            }
+        }
+
+        model.rho = fit_rho(&model, matches, &weights);
+        model
+    }
+
+    fn strength(&self, team_name: &str) -> (f64, f64) {
+        self.strengths.get(team_name).copied().unwrap_or((1.0, 1.0))
+    }
+
+    pub fn expected_goals(&self, home_team: &str, away_team: &str) -> (f64, f64) {
+        let (home_attack, home_defence) = self.strength(home_team);
+        let (away_attack, away_defence) = self.strength(away_team);
+
+        (
+            self.average_goals * self.home_advantage * home_attack * away_defence,
+            self.average_goals * away_attack * home_defence,
+        )
+    }
+
+    // Probability of every score up to `MAX_GOALS`, indexed [home goals][away goals].
+    pub fn score_probabilities(&self, home_team: &str, away_team: &str) -> Vec<Vec<f64>> {
+        let (home_mean, away_mean) = self.expected_goals(home_team, away_team);
+        let home_pmf = poisson_pmf(home_mean);
+        let away_pmf = poisson_pmf(away_mean);
+
+        let mut probabilities: Vec<Vec<f64>> = (0..=MAX_GOALS)
+            .map(|home_goals| {
+                (0..=MAX_GOALS)
+                    .map(|away_goals| {
+                        home_pmf[home_goals]
+                            * away_pmf[away_goals]
+                            * tau(home_goals, away_goals, home_mean, away_mean, self.rho).max(0.0)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let total: f64 = probabilities.iter().flatten().sum();
+        probabilities
+            .iter_mut()
+            .flatten()
+            .for_each(|probability| *probability /= total);
+        probabilities
+    }
+
+    // Home win, draw and away win probabilities.
+    pub fn outcome_probabilities(&self, home_team: &str, away_team: &str) -> (f64, f64, f64) {
+        let mut outcome = (0.0, 0.0, 0.0);
+        for (home_goals, row) in self
+            .score_probabilities(home_team, away_team)
+            .iter()
+            .enumerate()
+        {
+            for (away_goals, probability) in row.iter().enumerate() {
+                if home_goals > away_goals {
+                    outcome.0 += probability;
+                } else if home_goals == away_goals {
+                    outcome.1 += probability;
+                } else {
+                    outcome.2 += probability;
+                }
+            }
+        }
+        outcome
+    }
+
+    pub fn most_likely_scores(
+        &self,
+        home_team: &str,
+        away_team: &str,
+        count: usize,
+    ) -> Vec<((usize, usize), f64)> {
+        let mut scores: Vec<((usize, usize), f64)> = self
+            .score_probabilities(home_team, away_team)
+            .iter()
+            .enumerate()
+            .flat_map(|(home_goals, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(move |(away_goals, probability)| ((home_goals, away_goals), *probability))
+            })
+            .collect();
+
+        scores.sort_by(|(_, probability1), (_, probability2)| {
+            probability2.partial_cmp(probability1).unwrap()
+        });
+        scores.truncate(count);
+        scores
+    }
+
+    pub fn print_prediction(&self, home_team: &str, away_team: &str) {
+        let (home_mean, away_mean) = self.expected_goals(home_team, away_team);
+        let (home_win, draw, away_win) = self.outcome_probabilities(home_team, away_team);
+
+        println!("{} - {}", home_team, away_team);
+        println!("Expected goals: {:.2} - {:.2}", home_mean, away_mean);
+        println!(
+            "1: {:.1}%  X: {:.1}%  2: {:.1}%",
+            100.0 * home_win,
+            100.0 * draw,
+            100.0 * away_win
+        );
+        println!("Most likely scores:");
+        for ((home_goals, away_goals), probability) in
+            self.most_likely_scores(home_team, away_team, 5)
+        {
+            println!(
+                "  {}-{}  {:.1}%",
+                home_goals,
+                away_goals,
+                100.0 * probability
+            );
+        }
+    }
+}
+
+impl MatchModel for DixonColesModel {
+    fn sample_score(&self, home_team: &str, away_team: &str, rng: &mut Rng) -> (i32, i32) {
+        let mut remaining = rng.next_f64();
+        let probabilities = self.score_probabilities(home_team, away_team);
+
+        for (home_goals, row) in probabilities.iter().enumerate() {
+            for (away_goals, probability) in row.iter().enumerate() {
+                remaining -= probability;
+                if remaining < 0.0 {
+                    return (home_goals as i32, away_goals as i32);
+                }
+            }
+        }
+        (0, 0)
+    }
+}
+
+// Weight of every match, decaying with its age relative to the latest match in the log.
+fn match_weights(matches: &[Match], decay_per_day: f64) -> Vec<f64> {
+    let latest_kick_off: Option<NaiveDateTime> =
+        matches.iter().filter_map(|game| game.kick_off).max();
+    let latest_round = matches.iter().map(|game| game.round).max().unwrap_or(0);
+
+    matches
+        .iter()
+        .map(|game| {
+            let age_in_days = match (game.kick_off, latest_kick_off) {
+                (Some(kick_off), Some(latest)) => (latest - kick_off).num_hours() as f64 / 24.0,
+                _ => (latest_round - game.round) as f64 * DAYS_PER_ROUND,
+            };
+            (-decay_per_day * age_in_days.max(0.0)).exp()
+        })
+        .collect()
+}
+
+fn poisson_pmf(mean: f64) -> Vec<f64> {
+    let mut pmf = Vec::with_capacity(MAX_GOALS + 1);
+    let mut probability = (-mean).exp();
+    for goals in 0..=MAX_GOALS {
+        pmf.push(probability);
+        probability *= mean / (goals + 1) as f64;
+    }
+    pmf
+}
+
+// Dixon-Coles adjustment of the independent Poisson probabilities for 0-0, 1-0, 0-1 and 1-1.
+fn tau(home_goals: usize, away_goals: usize, home_mean: f64, away_mean: f64, rho: f64) -> f64 {
+    match (home_goals, away_goals) {
+        (0, 0) => 1.0 - home_mean * away_mean * rho,
+        (0, 1) => 1.0 + home_mean * rho,
+        (1, 0) => 1.0 + away_mean * rho,
+        (1, 1) => 1.0 - rho,
+        _ => 1.0,
+    }
+}
+
+// Rho maximising the weighted likelihood of the low scores, found by a grid search over the
+// range where all adjustments stay positive for typical scoring rates.
+fn fit_rho(model: &DixonColesModel, matches: &[Match], weights: &[f64]) -> f64 {
+    let likelihood = |rho: f64| -> f64 {
+        matches
+            .iter()
+            .zip(weights)
+            .map(|(game, weight)| {
+                let (home_mean, away_mean) = model.expected_goals(&game.home_team, &game.away_team);
+                let adjustment = tau(
+                    game.home_score as usize,
+                    game.away_score as usize,
+                    home_mean,
+                    away_mean,
+                    rho,
+                );
+                weight * adjustment.max(1e-9).ln()
+            })
+            .sum()
+    };
+
+    (-20..=20)
+        .map(|step| step as f64 / 100.0)
+        .max_by(|rho1, rho2| likelihood(*rho1).partial_cmp(&likelihood(*rho2)).unwrap())
+        .unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standings::matches;
+
+    fn create_test_matches() -> (Vec<String>, Vec<Match>) {
+        let team_names = vec![
+            String::from("FC Test"),
+            String::from("Foo"),
+            String::from("Bar"),
+        ];
+        let played = vec![
+            matches::create_match("FC Test", "Foo", 3, 0, 1),
+            matches::create_match("Bar", "FC Test", 0, 2, 2),
+            matches::create_match("Foo", "Bar", 1, 1, 3),
+            matches::create_match("Foo", "FC Test", 0, 4, 4),
+            matches::create_match("FC Test", "Bar", 2, 1, 5),
+            matches::create_match("Bar", "Foo", 2, 0, 6),
+        ];
+        (team_names, played)
+    }
+
+    #[test]
+    fn test_fit_strong_team() {
+        let (team_names, played) = create_test_matches();
+        let model = DixonColesModel::fit(&team_names, &played, &FitConfig::default());
+
+        let (home_win, draw, away_win) = model.outcome_probabilities("FC Test", "Foo");
+        assert!(home_win > away_win);
+        assert!((home_win + draw + away_win - 1.0).abs() < 1e-9);
+
+        let (strong_away, weak_home) = {
+            let (home, away) = model.expected_goals("Foo", "FC Test");
+            (away, home)
+        };
+        assert!(strong_away > weak_home);
+    }
+
+    #[test]
+    fn test_fit_without_matches() {
+        let (team_names, _) = create_test_matches();
+        let model = DixonColesModel::fit(&team_names, &[], &FitConfig::default());
+
+        assert_eq!(model.expected_goals("Foo", "Bar"), (1.35, 1.35));
+        assert_eq!(model.rho, 0.0);
+    }
+
+    #[test]
+    fn test_score_probabilities_sum_to_one() {
+        let (team_names, played) = create_test_matches();
+        let model = DixonColesModel::fit(&team_names, &played, &FitConfig::default());
+
+        let total: f64 = model
+            .score_probabilities("Bar", "FC Test")
+            .iter()
+            .flatten()
+            .sum();
+        assert!((total - 1.0).abs() < 1e-9);
+
+        let scores = model.most_likely_scores("Bar", "FC Test", 3);
+        assert_eq!(scores.len(), 3);
+        assert!(scores[0].1 >= scores[1].1);
+    }
+
+    #[test]
+    fn test_match_weights_decay() {
+        let (_, played) = create_test_matches();
+
+        let weights = match_weights(&played, 0.01);
+        assert_eq!(weights[5], 1.0);
+        assert!(weights[0] < weights[4]);
+        assert!(match_weights(&played, 0.0)
+            .iter()
+            .all(|weight| *weight == 1.0));
+    }
+
+    #[test]
+    fn test_tau() {
+        assert_eq!(tau(2, 3, 1.5, 1.2, 0.1), 1.0);
+        assert_eq!(tau(1, 1, 1.5, 1.2, 0.1), 0.9);
+        assert!(tau(0, 0, 1.5, 1.2, -0.1) > 1.0);
+    }
+
+    #[test]
+    fn test_sample_score_reproducible() {
+        let (team_names, played) = create_test_matches();
+        let model = DixonColesModel::fit(&team_names, &played, &FitConfig::default());
+
+        let mut rng1 = Rng::new(5);
+        let mut rng2 = Rng::new(5);
+        for _ in 0..20 {
+            assert_eq!(
+                model.sample_score("FC Test", "Bar", &mut rng1),
+                model.sample_score("FC Test", "Bar", &mut rng2)
+            );
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use std::cmp::Ordering;
+
+use crate::standings::prediction::DixonColesModel;
+use crate::standings::tables::Table;
+use crate::standings::team::TeamStats;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProjectedTeam {
+    pub name: String,
+    pub played: u32,
+    pub points: u32,
+    pub remaining: usize,
+    pub projected_points: f64,
+}
+
+pub fn points_per_game(team: &TeamStats) -> f64 {
+    if team.played == 0 {
+        0.0
+    } else {
+        team.points as f64 / team.played as f64
+    }
+}
+
+// Orders teams by points per game, which is fairer than points while some teams have games in
+// hand. Ties fall back to the normal table order.
+pub fn points_per_game_table(table: &Table) -> Table {
+    let mut ppg_table = table.clone();
+    ppg_table.update_table();
+    ppg_table.teams.sort_by(|team1, team2| {
+        points_per_game(team2)
+            .partial_cmp(&points_per_game(team1))
+            .unwrap_or(Ordering::Equal)
+    });
+    ppg_table
+}
+
+pub fn print_points_per_game_table(table: &Table) {
+    let mut ppg_table = points_per_game_table(table);
+    let ppg: Vec<String> = ppg_table
+        .teams
+        .iter()
+        .map(|team| format!("{:.2}", points_per_game(team)))
+        .collect();
+
+    println!("Points per game");
+    ppg_table.print_with_columns(&[("PPG", ppg)]);
+}
+
+// Extrapolates every team's current points per game over its remaining games. This only needs
+// the number of games left, so it also works for a table without a schedule or match log.
+pub fn project_by_points_per_game(table: &Table) -> Vec<ProjectedTeam> {
+    let mut current = table.clone();
+    current.update_table();
+
+    let mut projection: Vec<ProjectedTeam> = current
+        .teams
+        .iter()
+        .map(|team| {
+            let remaining = current.remaining_games(&team.name);
+            ProjectedTeam {
+                name: team.name.clone(),
+                played: team.played,
+                points: team.points,
+                remaining,
+                projected_points: team.points as f64 + points_per_game(team) * remaining as f64,
+            }
+        })
+        .collect();
+
+    sort_by_projected_points(&mut projection);
+    projection
+}
+
+// Adds the expected points of every remaining fixture according to the model. Fails when the
+// remaining fixtures are unknown.
+pub fn project_with_model(
+    table: &Table,
+    model: &DixonColesModel,
+) -> Result<Vec<ProjectedTeam>, &'static str> {
+    let mut current = table.clone();
+    current.update_table();
+
+    project(&current, |home_team, away_team| {
+        let (home_win, draw, away_win) =
+            model.outcome_probabilities(&home_team.name, &away_team.name);
+        (3.0 * home_win + draw, 3.0 * away_win + draw)
+    })
+}
+
+// Sums the expected points of both sides over the remaining fixtures and orders the teams by
+// projected final points.
+fn project<F>(table: &Table, expected_points: F) -> Result<Vec<ProjectedTeam>, &'static str>
+where
+    F: Fn(&TeamStats, &TeamStats) -> (f64, f64),
+{
+    let mut projection: Vec<ProjectedTeam> = table
+        .teams
+        .iter()
+        .map(|team| ProjectedTeam {
+            name: team.name.clone(),
+            played: team.played,
+            points: team.points,
+            remaining: 0,
+            projected_points: team.points as f64,
+        })
+        .collect();
+
+    for fixture in table.remaining_fixtures()? {
+        let home = table
+            .teams
+            .iter()
+            .position(|team| team.name == fixture.home_team);
+        let away = table
+            .teams
+            .iter()
+            .position(|team| team.name == fixture.away_team);
+        let (Some(home), Some(away)) = (home, away) else {
+            continue;
+        };
+
+        let (home_points, away_points) = expected_points(&table.teams[home], &table.teams[away]);
+        projection[home].remaining += 1;
+        projection[home].projected_points += home_points;
+        projection[away].remaining += 1;
+        projection[away].projected_points += away_points;
+    }
+
+    sort_by_projected_points(&mut projection);
+    Ok(projection)
+}
+
+fn sort_by_projected_points(projection: &mut [ProjectedTeam]) {
+    projection.sort_by(|team1, team2| {
+        team2
+            .projected_points
+            .partial_cmp(&team1.projected_points)
+            .unwrap_or(Ordering::Equal)
+    });
+}
+
+pub fn print_projection(projection: &[ProjectedTeam]) {
+    println!("Projected final table");
+
+    let column_width: usize = projection
+        .iter()
+        .map(|team| team.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+
+    println!(
+        "{:<width$} {:>2}  {:>2}  {:>4}  {:>5}",
+        "Club",
+        "G",
+        "P",
+        "Left",
+        "Proj",
+        width = column_width
+    );
+    for team in projection {
+        println!(
+            "{:<width$} {:>2}  {:>2}  {:>4}  {:>5.1}",
+            team.name,
+            team.played,
+            team.points,
+            team.remaining,
+            team.projected_points,
+            width = column_width
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standings::tables;
+
+    fn create_test_table() -> Table {
+        let team_names = vec![
+            String::from("FC Test"),
+            String::from("Foo"),
+            String::from("Bar"),
+        ];
+
+        // Foo has a game in hand on both other teams.
+        let mut test_table = tables::create_table(&team_names);
+        test_table.add_game("FC Test", "Bar", 0, 0);
+        test_table.add_game("Bar", "Foo", 0, 1);
+        test_table.add_game("Bar", "FC Test", 2, 1);
+        test_table.add_game("FC Test", "Foo", 3, 0);
+        test_table.update_table();
+        test_table
+    }
+
+    #[test]
+    fn test_points_per_game_table() {
+        let test_table = create_test_table();
+        let ppg_table = points_per_game_table(&test_table);
+
+        // FC Test and Bar have 4 points from 3 games, Foo 3 points from 2.
+        assert_eq!(ppg_table.teams[0].name, "Foo");
+        assert_eq!(points_per_game(&ppg_table.teams[0]), 1.5);
+        assert_eq!(test_table.teams[2].name, "Foo");
+    }
+
+    #[test]
+    fn test_project_by_points_per_game() {
+        let projection = project_by_points_per_game(&create_test_table());
+
+        assert_eq!(projection[0].name, "Foo");
+        assert_eq!(projection[0].remaining, 2);
+        assert_eq!(projection[0].projected_points, 6.0);
+        assert_eq!(projection[1].remaining, 1);
+        assert!((projection[1].projected_points - 4.0 - 4.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_project_by_points_per_game_without_match_log() {
+        // Records as read from a table file, with no schedule or match log behind them.
+        let mut test_table = create_test_table();
+        test_table.matches.clear();
+        assert!(!test_table.has_known_schedule());
+
+        let projection = project_by_points_per_game(&test_table);
+        assert_eq!(projection[0].name, "Foo");
+        assert_eq!(projection[0].remaining, 2);
+        assert_eq!(projection[1].remaining, 1);
+        assert!((projection[1].projected_points - 4.0 - 4.0 / 3.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,110 @@
+// Small seedable pseudo random number generator (SplitMix64). Simulations and draws only need
+// reproducible, reasonably distributed numbers, not cryptographic quality.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    // A generator for one of many independent streams derived from the same seed, so results
+    // do not depend on how work is split between threads.
+    pub fn for_stream(seed: u64, stream: u64) -> Rng {
+        let mut mixer = Rng::new(seed ^ stream.wrapping_mul(0xD1B5_4A32_D192_ED03));
+        Rng::new(mixer.next_u64())
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in [0, 1).
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // Uniform in [0, upper). `upper` must be greater than zero.
+    pub fn below(&mut self, upper: usize) -> usize {
+        (self.next_f64() * upper as f64) as usize
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+
+    // Number of events of a Poisson process with the given mean (Knuth's method, which is fine
+    // for football scores).
+    pub fn poisson(&mut self, mean: f64) -> i32 {
+        let limit = (-mean).exp();
+        let mut count = 0;
+        let mut product = self.next_f64();
+
+        while product > limit {
+            count += 1;
+            product *= self.next_f64();
+        }
+
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_numbers() {
+        let mut first = Rng::new(42);
+        let mut second = Rng::new(42);
+
+        for _ in 0..10 {
+            assert_eq!(first.next_u64(), second.next_u64());
+        }
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+        assert_ne!(
+            Rng::for_stream(1, 0).next_u64(),
+            Rng::for_stream(1, 1).next_u64()
+        );
+    }
+
+    #[test]
+    fn test_next_f64_and_below() {
+        let mut rng = Rng::new(7);
+
+        for _ in 0..1000 {
+            let value = rng.next_f64();
+            assert!((0.0..1.0).contains(&value));
+            assert!(rng.below(5) < 5);
+        }
+    }
+
+    #[test]
+    fn test_shuffle_keeps_items() {
+        let mut rng = Rng::new(3);
+        let mut items = vec![1, 2, 3, 4, 5, 6];
+
+        rng.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, vec![1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_poisson_mean() {
+        let mut rng = Rng::new(11);
+        let samples = 20000;
+
+        let total: i32 = (0..samples).map(|_| rng.poisson(1.5)).sum();
+        let mean = total as f64 / samples as f64;
+        assert!((mean - 1.5).abs() < 0.05);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::standings::matches::Match;
+use crate::standings::tables::Table;
+
+// Hypothetical results for upcoming fixtures, applied to a copy of the real table.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scenario {
+    pub name: String,
+    pub results: Vec<Match>,
+}
+
+impl Scenario {
+    // Adds a hypothetical result if it is one of the table's remaining fixtures that the
+    // scenario has not already decided.
+    pub fn add_result(&mut self, table: &Table, game: Match) -> Result<(), &'static str> {
+        let remaining = self.apply(table).remaining_fixtures()?;
+        if !remaining.iter().any(|fixture| fixture.is_played_as(&game)) {
+            return Err("The fixture has already been played or is not scheduled.");
+        }
+
+        self.results.push(game);
+        Ok(())
+    }
+
+    // The table as it would look with the scenario's results added.
+    pub fn apply(&self, table: &Table) -> Table {
+        let mut scenario_table = table.clone();
+        for game in &self.results {
+            scenario_table.add_match(game.clone());
+        }
+        scenario_table.update_table();
+        scenario_table
+    }
+
+    pub fn print(&self, table: &Table) {
+        let mut current = table.clone();
+        current.update_table();
+        let mut scenario_table = self.apply(table);
+        let movement = scenario_table.movement_since(&current);
+
+        println!("Scenario \"{}\"", self.name);
+        self.results.iter().for_each(|game| println!("  {}", game));
+        scenario_table.print_with_columns(&[("+/-", movement)]);
+    }
+}
+
+pub fn create_scenario(name: &str) -> Scenario {
+    Scenario {
+        name: name.to_string(),
+        results: Vec::new(),
+    }
+}
+
+// One column per scenario with the position, points and movement of every team, in the order
+// of the real table.
+pub fn comparison_columns(table: &Table, scenarios: &[Scenario]) -> Vec<(String, Vec<String>)> {
+    scenarios
+        .iter()
+        .map(|scenario| {
+            let scenario_table = scenario.apply(table);
+            let movement = scenario_table.movement_since(table);
+
+            let values = table
+                .teams
+                .iter()
+                .map(|team| {
+                    let position = scenario_table.position(&team.name).unwrap_or(0);
+                    let points = scenario_table.teams[position - 1].points;
+                    format!("{:>2}. {:>3}p {}", position, points, movement[position - 1])
+                })
+                .collect();
+
+            (scenario.name.clone(), values)
+        })
+        .collect()
+}
+
+pub fn print_comparison(table: &Table, scenarios: &[Scenario]) {
+    let mut current = table.clone();
+    current.update_table();
+
+    let columns = comparison_columns(&current, scenarios);
+    let column_refs: Vec<(&str, Vec<String>)> = columns
+        .iter()
+        .map(|(name, values)| (name.as_str(), values.clone()))
+        .collect();
+
+    println!("Scenario comparison");
+    current.print_with_columns(&column_refs);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standings::matches;
+    use crate::standings::tables;
+
+    fn create_test_table() -> Table {
+        let team_names = vec![
+            String::from("FC Test"),
+            String::from("Foo"),
+            String::from("Bar"),
+        ];
+
+        let mut test_table = tables::create_table(&team_names);
+        test_table.add_game("FC Test", "Foo", 2, 0);
+        test_table.update_table();
+        test_table
+    }
+
+    #[test]
+    fn test_apply_leaves_table_untouched() {
+        let test_table = create_test_table();
+        let mut scenario = create_scenario("Bar wins");
+
+        scenario
+            .add_result(
+                &test_table,
+                matches::create_match("Bar", "FC Test", 5, 0, 2),
+            )
+            .unwrap();
+        let scenario_table = scenario.apply(&test_table);
+
+        assert_eq!(scenario_table.teams[0].name, "Bar");
+        assert_eq!(scenario_table.matches.len(), 2);
+        assert_eq!(test_table.matches.len(), 1);
+        assert_eq!(test_table.teams[0].name, "FC Test");
+    }
+
+    #[test]
+    fn test_add_result_only_for_remaining_fixtures() {
+        let test_table = create_test_table();
+        let mut scenario = create_scenario("Test");
+
+        assert!(scenario
+            .add_result(
+                &test_table,
+                matches::create_match("FC Test", "Foo", 0, 1, 2)
+            )
+            .is_err());
+        assert!(scenario
+            .add_result(
+                &test_table,
+                matches::create_match("Foo", "FC Test", 0, 1, 2)
+            )
+            .is_ok());
+        assert!(scenario
+            .add_result(
+                &test_table,
+                matches::create_match("Foo", "FC Test", 3, 1, 3)
+            )
+            .is_err());
+        assert_eq!(scenario.results.len(), 1);
+    }
+
+    #[test]
+    fn test_comparison_columns() {
+        let test_table = create_test_table();
+        let mut foo_wins = create_scenario("Foo wins");
+        foo_wins
+            .add_result(&test_table, matches::create_match("Foo", "Bar", 1, 0, 2))
+            .unwrap();
+        let mut bar_wins = create_scenario("Bar wins");
+        bar_wins
+            .add_result(&test_table, matches::create_match("Foo", "Bar", 0, 4, 2))
+            .unwrap();
+
+        let columns = comparison_columns(&test_table, &[foo_wins, bar_wins]);
+        assert_eq!(columns.len(), 2);
+        assert_eq!(columns[0].0, "Foo wins");
+        // Real table order is FC Test, Bar, Foo.
+        assert_eq!(columns[0].1[2], " 2.   3p ▲1");
+        assert_eq!(columns[1].1[1], " 1.   3p ▲1");
+        assert_eq!(columns[1].1[0], " 2.   3p ▼1");
+    }
+}
@@ -0,0 +1,314 @@
+use crate::standings::matches::{Match, Venue};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum GoalType {
+    #[default]
+    Normal,
+    Penalty,
+    // Scored by a player of the other team than the one credited with the goal.
+    OwnGoal,
+}
+
+// A goal of a match, credited to the team playing at `venue`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Goal {
+    pub scorer: String,
+    pub minute: u32,
+    // Minutes into added time, e.g. 2 for "45+2'".
+    pub added_time: u32,
+    pub goal_type: GoalType,
+    pub venue: Venue,
+}
+
+impl Goal {
+    // The team the scorer plays for, which for an own goal is the opponent of the team
+    // credited with the goal.
+    pub fn scorer_team<'a>(&self, game: &'a Match) -> &'a str {
+        match (self.venue, self.goal_type) {
+            (Venue::Home, GoalType::OwnGoal)
+            | (Venue::Away, GoalType::Normal | GoalType::Penalty) => &game.away_team,
+            _ => &game.home_team,
+        }
+    }
+}
+
+// A row of the top-scorer table (skytteliga). Penalties are included in `goals`, own goals
+// are not.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ScorerStats {
+    pub name: String,
+    pub team: String,
+    pub goals: u32,
+    pub penalties: u32,
+    pub own_goals: u32,
+}
+
+// Parses one goal event such as "Berg 34'", "Rosenberg 45+2' (pen)" or "Larsson 12' (og)".
+pub fn parse_goal(goal_str: &str, venue: Venue) -> Result<Goal, &'static str> {
+    let mut event = goal_str.trim();
+    let mut goal_type = GoalType::Normal;
+    if let Some(rest) = event.strip_suffix("(pen)") {
+        goal_type = GoalType::Penalty;
+        event = rest.trim_end();
+    } else if let Some(rest) = event.strip_suffix("(og)") {
+        goal_type = GoalType::OwnGoal;
+        event = rest.trim_end();
+    }
+
+    let (scorer, minute_str) = event
+        .rsplit_once(' ')
+        .ok_or("Invalid goal format, use \"name minute'\".")?;
+    let (minute, added_time) = parse_minute(minute_str)?;
+    if scorer.trim().is_empty() {
+        return Err("The goal scorer is missing.");
+    }
+
+    Ok(Goal {
+        scorer: scorer.trim().to_string(),
+        minute,
+        added_time,
+        goal_type,
+        venue,
+    })
+}
+
+// Parses a match minute such as "34'" or "45+2'" into the minute and the added time.
+pub fn parse_minute(minute_str: &str) -> Result<(u32, u32), &'static str> {
+    let minute_str = minute_str
+        .trim()
+        .strip_suffix('\'')
+        .ok_or("The minute must end with ', e.g. 34'.")?;
+    let (minute, added_time) = minute_str.split_once('+').unwrap_or((minute_str, "0"));
+
+    let minute = minute
+        .parse::<u32>()
+        .map_err(|_| "Cannot parse the minute.")?;
+    let added_time = added_time
+        .parse::<u32>()
+        .map_err(|_| "Cannot parse the minute.")?;
+    Ok((minute, added_time))
+}
+
+pub fn format_minute(minute: u32, added_time: u32) -> String {
+    if added_time > 0 {
+        format!("{}+{}'", minute, added_time)
+    } else {
+        format!("{}'", minute)
+    }
+}
+
+// Parses the goal field of a result line: the home team's goals, then "|" and the away
+// team's goals, each a comma separated list of goal events. Without "|" all goals are the
+// home team's. The number of goals must match the score.
+pub fn parse_goals(
+    goals_str: &str,
+    home_score: i32,
+    away_score: i32,
+) -> Result<Vec<Goal>, &'static str> {
+    let (home_str, away_str) = goals_str.split_once('|').unwrap_or((goals_str, ""));
+
+    let mut goals = Vec::new();
+    for (side_str, venue) in [(home_str, Venue::Home), (away_str, Venue::Away)] {
+        for goal_str in side_str.split(',').filter(|goal| !goal.trim().is_empty()) {
+            goals.push(parse_goal(goal_str, venue)?);
+        }
+    }
+
+    let count = |venue| goals.iter().filter(|goal| goal.venue == venue).count() as i32;
+    if count(Venue::Home) != home_score || count(Venue::Away) != away_score {
+        return Err("The goal events do not match the score.");
+    }
+    Ok(goals)
+}
+
+pub fn format_goal(goal: &Goal) -> String {
+    let mut event = format!(
+        "{} {}",
+        goal.scorer,
+        format_minute(goal.minute, goal.added_time)
+    );
+    match goal.goal_type {
+        GoalType::Normal => {}
+        GoalType::Penalty => event.push_str(" (pen)"),
+        GoalType::OwnGoal => event.push_str(" (og)"),
+    }
+    event
+}
+
+// Formats goals as the goal field of a result line that `parse_goals` reads back.
+pub fn format_goals(goals: &[Goal]) -> String {
+    let side = |venue| {
+        goals
+            .iter()
+            .filter(|goal| goal.venue == venue)
+            .map(format_goal)
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+    format!("{}|{}", side(Venue::Home), side(Venue::Away))
+}
+
+// The top-scorer table of the matches that were played out, most goals first.
+pub fn top_scorers(games: &[Match]) -> Vec<ScorerStats> {
+    let mut scorers: Vec<ScorerStats> = Vec::new();
+
+    for game in games.iter().filter(|game| game.counts_in_statistics()) {
+        for goal in &game.goals {
+            let team = goal.scorer_team(game);
+            let index = match scorers
+                .iter()
+                .position(|scorer| scorer.name == goal.scorer && scorer.team == team)
+            {
+                Some(index) => index,
+                None => {
+                    scorers.push(ScorerStats {
+                        name: goal.scorer.clone(),
+                        team: team.to_string(),
+                        ..Default::default()
+                    });
+                    scorers.len() - 1
+                }
+            };
+
+            let scorer = &mut scorers[index];
+            match goal.goal_type {
+                GoalType::Normal => scorer.goals += 1,
+                GoalType::Penalty => {
+                    scorer.goals += 1;
+                    scorer.penalties += 1;
+                }
+                GoalType::OwnGoal => scorer.own_goals += 1,
+            }
+        }
+    }
+
+    scorers.sort_by(|scorer1, scorer2| {
+        scorer2
+            .goals
+            .cmp(&scorer1.goals)
+            .then(scorer1.penalties.cmp(&scorer2.penalties))
+            .then(scorer1.name.cmp(&scorer2.name))
+    });
+    scorers
+}
+
+pub fn print_top_scorers(title: &str, scorers: &[ScorerStats]) {
+    println!("--------- {} top scorers ------------", title);
+    if scorers.is_empty() {
+        println!("No goal events have been entered.");
+        return;
+    }
+
+    let name_width = scorers
+        .iter()
+        .map(|scorer| scorer.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    let team_width = scorers
+        .iter()
+        .map(|scorer| scorer.team.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    println!(
+        "{:<4}{:<name_width$}  {:<team_width$}  {:>5}  {:>4}  {:>2}",
+        "",
+        "Player",
+        "Club",
+        "Goals",
+        "Pens",
+        "OG",
+        name_width = name_width,
+        team_width = team_width
+    );
+    for (index, scorer) in scorers.iter().enumerate() {
+        println!(
+            "{:<4}{:<name_width$}  {:<team_width$}  {:>5}  {:>4}  {:>2}",
+            format!("{}.", index + 1),
+            scorer.name,
+            scorer.team,
+            scorer.goals,
+            scorer.penalties,
+            scorer.own_goals,
+            name_width = name_width,
+            team_width = team_width
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standings::matches;
+
+    #[test]
+    fn test_parse_goal() {
+        let goal = parse_goal("Anders Berg 45+2' (pen)", Venue::Away).unwrap();
+
+        assert_eq!(goal.scorer, "Anders Berg");
+        assert_eq!(goal.minute, 45);
+        assert_eq!(goal.added_time, 2);
+        assert_eq!(goal.goal_type, GoalType::Penalty);
+        assert_eq!(format_goal(&goal), "Anders Berg 45+2' (pen)");
+    }
+
+    #[test]
+    fn test_parse_goals() {
+        let goals = parse_goals("Berg 34', Larsson 80' (og)|Ali 60'", 2, 1).unwrap();
+
+        assert_eq!(goals.len(), 3);
+        assert_eq!(goals[1].goal_type, GoalType::OwnGoal);
+        assert_eq!(goals[2].venue, Venue::Away);
+        assert_eq!(format_goals(&goals), "Berg 34', Larsson 80' (og)|Ali 60'");
+        assert_eq!(parse_goals("Berg 34'", 1, 0).unwrap()[0].venue, Venue::Home);
+    }
+
+    #[test]
+    #[should_panic(expected = "The goal events do not match the score.")]
+    fn test_parse_goals_panic_wrong_count() {
+        parse_goals("Berg 34'|Ali 60'", 2, 1).unwrap();
+    }
+
+    #[test]
+    fn test_top_scorers() {
+        let game = |home_team, away_team, home_score, away_score, goals_str| Match {
+            goals: parse_goals(goals_str, home_score, away_score).unwrap(),
+            ..matches::create_match(home_team, away_team, home_score, away_score, 1)
+        };
+        let games = vec![
+            game(
+                "Malmö FF",
+                "Kalmar FF",
+                2,
+                0,
+                "Berg 34', Rosenberg 70' (pen)",
+            ),
+            game(
+                "Kalmar FF",
+                "Malmö FF",
+                1,
+                2,
+                "Ali 10'|Berg 50', Nilsson 88' (og)",
+            ),
+            Match {
+                result_type: matches::ResultType::Annulled,
+                ..game("Malmö FF", "AIK", 1, 0, "Rosenberg 5'")
+            },
+        ];
+
+        let scorers = top_scorers(&games);
+        assert_eq!(scorers[0].name, "Berg");
+        assert_eq!(scorers[0].goals, 2);
+        assert_eq!(scorers[1].name, "Ali");
+        assert_eq!(scorers[2].name, "Rosenberg");
+        assert_eq!(scorers[2].penalties, 1);
+
+        let own_goal = scorers
+            .iter()
+            .find(|scorer| scorer.name == "Nilsson")
+            .unwrap();
+        assert_eq!(own_goal.team, "Kalmar FF");
+        assert_eq!(own_goal.goals, 0);
+        assert_eq!(own_goal.own_goals, 1);
+    }
+}
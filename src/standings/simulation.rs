@@ -0,0 +1,441 @@
+use std::collections::HashMap;
+use std::thread;
+
+use crate::standings::random::Rng;
+use crate::standings::tables;
+use crate::standings::tables::Table;
+use crate::standings::team::TeamStats;
+use crate::standings::zones::Zones;
+
+// Goals per game used by `TableModel` before any matches have been played.
+const DEFAULT_HOME_GOALS: f64 = 1.5;
+const DEFAULT_AWAY_GOALS: f64 = 1.2;
+// Number of average games mixed into each team's record so that strengths after a few
+// rounds are not extreme.
+const PRIOR_GAMES: f64 = 3.0;
+
+// Decides the score of a simulated match.
+pub trait MatchModel: Sync {
+    fn sample_score(&self, home_team: &str, away_team: &str, rng: &mut Rng) -> (i32, i32);
+}
+
+// Poisson goals based on each team's goals scored and conceded per game in the current table.
+pub struct TableModel {
+    // Attack and defence relative to the league average, 1.0 being an average team.
+    strengths: HashMap<String, (f64, f64)>,
+    home_goals: f64,
+    away_goals: f64,
+}
+
+impl TableModel {
+    pub fn from_table(table: &Table) -> TableModel {
+        let games: f64 = table
+            .teams
+            .iter()
+            .map(|team| team.played as f64)
+            .sum::<f64>()
+            / 2.0;
+        let goals: f64 = table.teams.iter().map(|team| team.goal_scored as f64).sum();
+
+        let (home_goals, away_goals) = if games > 0.0 {
+            let scale = goals / games / (DEFAULT_HOME_GOALS + DEFAULT_AWAY_GOALS);
+            (DEFAULT_HOME_GOALS * scale, DEFAULT_AWAY_GOALS * scale)
+        } else {
+            (DEFAULT_HOME_GOALS, DEFAULT_AWAY_GOALS)
+        };
+        let average = ((home_goals + away_goals) / 2.0).max(0.1);
+
+        let strengths = table
+            .teams
+            .iter()
+            .map(|team| {
+                let played = team.played as f64 + PRIOR_GAMES;
+                let attack = (team.goal_scored as f64 + PRIOR_GAMES * average) / played / average;
+                let defence = (team.goal_against as f64 + PRIOR_GAMES * average) / played / average;
+                (team.name.clone(), (attack, defence))
+            })
+            .collect();
+
+        TableModel {
+            strengths,
+            home_goals,
+            away_goals,
+        }
+    }
+
+    pub fn expected_goals(&self, home_team: &str, away_team: &str) -> (f64, f64) {
+        let (home_attack, home_defence) =
+            self.strengths.get(home_team).copied().unwrap_or((1.0, 1.0));
+        let (away_attack, away_defence) =
+            self.strengths.get(away_team).copied().unwrap_or((1.0, 1.0));
+
+        (
+            self.home_goals * home_attack * away_defence,
+            self.away_goals * away_attack * home_defence,
+        )
+    }
+}
+
+impl MatchModel for TableModel {
+    fn sample_score(&self, home_team: &str, away_team: &str, rng: &mut Rng) -> (i32, i32) {
+        let (home_mean, away_mean) = self.expected_goals(home_team, away_team);
+        (rng.poisson(home_mean), rng.poisson(away_mean))
+    }
+}
+
+pub struct SimulationConfig {
+    pub iterations: usize,
+    pub seed: u64,
+    pub threads: usize,
+    pub zones: Zones,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> SimulationConfig {
+        SimulationConfig {
+            iterations: 10000,
+            seed: 1,
+            threads: thread::available_parallelism().map_or(1, |threads| threads.get()),
+            zones: Zones::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TeamOutcome {
+    pub name: String,
+    // Number of simulated seasons the team finished in each position, first place first.
+    pub position_counts: Vec<usize>,
+    pub total_points: u64,
+}
+
+pub struct SimulationReport {
+    pub iterations: usize,
+    pub seed: u64,
+    pub zones: Zones,
+    // Ordered by average final position.
+    pub teams: Vec<TeamOutcome>,
+}
+
+impl SimulationReport {
+    pub fn probability(&self, outcome: &TeamOutcome, positions: std::ops::Range<usize>) -> f64 {
+        let count: usize = positions
+            .filter_map(|position| outcome.position_counts.get(position))
+            .sum();
+        count as f64 / self.iterations.max(1) as f64
+    }
+
+    pub fn title_probability(&self, outcome: &TeamOutcome) -> f64 {
+        self.probability(outcome, 0..self.zones.title_spots)
+    }
+
+    pub fn europe_probability(&self, outcome: &TeamOutcome) -> f64 {
+        self.probability(outcome, 0..self.zones.europe_spots)
+    }
+
+    pub fn playoff_probability(&self, outcome: &TeamOutcome) -> f64 {
+        let team_count = outcome.position_counts.len();
+        let first = self.zones.last_safe_position(team_count);
+        let last = self.zones.first_relegation_position(team_count) - 1;
+        self.probability(outcome, first..last)
+    }
+
+    pub fn relegation_probability(&self, outcome: &TeamOutcome) -> f64 {
+        let team_count = outcome.position_counts.len();
+        let first = self.zones.first_relegation_position(team_count) - 1;
+        self.probability(outcome, first..team_count)
+    }
+
+    pub fn average_points(&self, outcome: &TeamOutcome) -> f64 {
+        outcome.total_points as f64 / self.iterations.max(1) as f64
+    }
+
+    pub fn print(&self) {
+        println!("Simulated {} seasons (seed {})", self.iterations, self.seed);
+
+        let column_width: usize = self
+            .teams
+            .iter()
+            .map(|outcome| outcome.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(4);
+
+        println!(
+            "{:<width$} {:>5}  {:>6}  {:>6}  {:>7}  {:>6}",
+            "Club",
+            "Pts",
+            "Title",
+            "Europe",
+            "Playoff",
+            "Releg",
+            width = column_width
+        );
+        for outcome in &self.teams {
+            println!(
+                "{:<width$} {:>5.1}  {:>5.1}%  {:>5.1}%  {:>6.1}%  {:>5.1}%",
+                outcome.name,
+                self.average_points(outcome),
+                100.0 * self.title_probability(outcome),
+                100.0 * self.europe_probability(outcome),
+                100.0 * self.playoff_probability(outcome),
+                100.0 * self.relegation_probability(outcome),
+                width = column_width
+            );
+        }
+
+        println!();
+        println!("Final position probabilities (%)");
+        let positions: String = (1..=self.teams.len())
+            .map(|position| format!(" {:>4}", position))
+            .collect();
+        println!("{:<width$}{}", "Club", positions, width = column_width);
+        for outcome in &self.teams {
+            let percentages: String = (0..outcome.position_counts.len())
+                .map(|position| {
+                    let percentage = 100.0 * self.probability(outcome, position..position + 1);
+                    if percentage == 0.0 {
+                        format!(" {:>4}", "-")
+                    } else {
+                        format!(" {:>4.0}", percentage)
+                    }
+                })
+                .collect();
+            println!(
+                "{:<width$}{}",
+                outcome.name,
+                percentages,
+                width = column_width
+            );
+        }
+    }
+}
+
+// Plays the remaining fixtures of the table `config.iterations` times with the given model
+// and counts where every team finishes. Every season uses its own random stream derived from
+// the seed, so the report is the same regardless of the number of threads.
+pub fn simulate_season(
+    table: &Table,
+    model: &dyn MatchModel,
+    config: &SimulationConfig,
+) -> Result<SimulationReport, &'static str> {
+    let mut base_teams: Vec<TeamStats> = table.teams.clone();
+    base_teams.iter_mut().for_each(|team| team.calc_stats());
+
+    let fixtures: Vec<(usize, usize)> = table
+        .remaining_fixtures()?
+        .iter()
+        .filter_map(|fixture| {
+            let home = base_teams
+                .iter()
+                .position(|team| team.name == fixture.home_team)?;
+            let away = base_teams
+                .iter()
+                .position(|team| team.name == fixture.away_team)?;
+            Some((home, away))
+        })
+        .collect();
+
+    let team_count = base_teams.len();
+    let threads = config.threads.max(1);
+
+    let partial_results: Vec<(Vec<Vec<usize>>, Vec<u64>)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|thread_index| {
+                let base_teams = &base_teams;
+                let fixtures = &fixtures;
+                scope.spawn(move || {
+                    let mut position_counts = vec![vec![0; team_count]; team_count];
+                    let mut total_points = vec![0; team_count];
+
+                    for iteration in (thread_index..config.iterations).step_by(threads) {
+                        let mut rng = Rng::for_stream(config.seed, iteration as u64);
+                        let final_teams = simulate_once(base_teams, fixtures, model, &mut rng);
+
+                        for (position, team_index) in final_order(&final_teams).iter().enumerate() {
+                            position_counts[*team_index][position] += 1;
+                            total_points[*team_index] += final_teams[*team_index].points as u64;
+                        }
+                    }
+
+                    (position_counts, total_points)
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    });
+
+    let mut teams: Vec<TeamOutcome> = base_teams
+        .iter()
+        .map(|team| TeamOutcome {
+            name: team.name.clone(),
+            position_counts: vec![0; team_count],
+            total_points: 0,
+        })
+        .collect();
+
+    for (position_counts, total_points) in partial_results {
+        for (team_index, outcome) in teams.iter_mut().enumerate() {
+            for (position, count) in position_counts[team_index].iter().enumerate() {
+                outcome.position_counts[position] += count;
+            }
+            outcome.total_points += total_points[team_index];
+        }
+    }
+
+    let average_position = |outcome: &TeamOutcome| -> f64 {
+        outcome
+            .position_counts
+            .iter()
+            .enumerate()
+            .map(|(position, count)| (position + 1) as f64 * *count as f64)
+            .sum()
+    };
+    teams.sort_by(|outcome1, outcome2| {
+        average_position(outcome1)
+            .partial_cmp(&average_position(outcome2))
+            .unwrap()
+    });
+
+    Ok(SimulationReport {
+        iterations: config.iterations,
+        seed: config.seed,
+        zones: config.zones.clone(),
+        teams,
+    })
+}
+
+fn simulate_once(
+    base_teams: &[TeamStats],
+    fixtures: &[(usize, usize)],
+    model: &dyn MatchModel,
+    rng: &mut Rng,
+) -> Vec<TeamStats> {
+    let mut teams = base_teams.to_vec();
+
+    for (home, away) in fixtures {
+        let (home_score, away_score) =
+            model.sample_score(&teams[*home].name, &teams[*away].name, rng);
+        teams[*home].add_result(home_score, away_score);
+        teams[*away].add_result(away_score, home_score);
+    }
+
+    teams.iter_mut().for_each(|team| team.calc_stats());
+    teams
+}
+
+// Team indexes ordered the same way as `Table::update_table`.
+fn final_order(teams: &[TeamStats]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..teams.len()).collect();
+    order.sort_by(|index1, index2| tables::compare_teams(&teams[*index1], &teams[*index2]));
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standings::matches;
+
+    // Home team always wins 1-0.
+    struct HomeWinModel;
+
+    impl MatchModel for HomeWinModel {
+        fn sample_score(&self, _home_team: &str, _away_team: &str, _rng: &mut Rng) -> (i32, i32) {
+            (1, 0)
+        }
+    }
+
+    fn create_test_table() -> Table {
+        let team_names = vec![
+            String::from("FC Test"),
+            String::from("Foo"),
+            String::from("Bar"),
+            String::from("Baz"),
+        ];
+
+        let mut test_table = tables::create_table(&team_names);
+        test_table.add_match(matches::create_match("FC Test", "Foo", 5, 0, 1));
+        test_table.add_match(matches::create_match("Bar", "Baz", 4, 0, 1));
+        test_table.add_match(matches::create_match("FC Test", "Bar", 2, 0, 2));
+        test_table.add_match(matches::create_match("Foo", "Baz", 0, 3, 2));
+        test_table.update_table();
+        test_table
+    }
+
+    fn create_test_config(threads: usize) -> SimulationConfig {
+        SimulationConfig {
+            iterations: 500,
+            seed: 7,
+            threads,
+            zones: Zones {
+                title_spots: 1,
+                europe_spots: 1,
+                playoff_spots: 1,
+                relegation_spots: 1,
+            },
+        }
+    }
+
+    #[test]
+    fn test_simulate_season_deterministic_model() {
+        let test_table = create_test_table();
+        let report = simulate_season(&test_table, &HomeWinModel, &create_test_config(2)).unwrap();
+
+        // Baz has 3 points and three remaining home games, 12 points in total.
+        assert_eq!(report.teams[0].name, "Baz");
+        assert_eq!(report.title_probability(&report.teams[0]), 1.0);
+        assert_eq!(report.average_points(&report.teams[0]), 12.0);
+        for outcome in &report.teams {
+            assert_eq!(outcome.position_counts.iter().sum::<usize>(), 500);
+        }
+    }
+
+    #[test]
+    fn test_simulate_season_reproducible_across_threads() {
+        let test_table = create_test_table();
+        let model = TableModel::from_table(&test_table);
+
+        let single = simulate_season(&test_table, &model, &create_test_config(1)).unwrap();
+        let parallel = simulate_season(&test_table, &model, &create_test_config(4)).unwrap();
+
+        for (outcome1, outcome2) in single.teams.iter().zip(&parallel.teams) {
+            assert_eq!(outcome1.name, outcome2.name);
+            assert_eq!(outcome1.position_counts, outcome2.position_counts);
+            assert_eq!(outcome1.total_points, outcome2.total_points);
+        }
+    }
+
+    #[test]
+    fn test_zone_probabilities_sum_to_one() {
+        let test_table = create_test_table();
+        let model = TableModel::from_table(&test_table);
+        let report = simulate_season(&test_table, &model, &create_test_config(2)).unwrap();
+
+        let titles: f64 = report
+            .teams
+            .iter()
+            .map(|outcome| report.title_probability(outcome))
+            .sum();
+        let relegations: f64 = report
+            .teams
+            .iter()
+            .map(|outcome| report.relegation_probability(outcome))
+            .sum();
+        assert!((titles - 1.0).abs() < 1e-9);
+        assert!((relegations - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_table_model_expected_goals() {
+        let model = TableModel::from_table(&create_test_table());
+
+        let (strong_home, weak_away) = model.expected_goals("FC Test", "Foo");
+        let (weak_home, strong_away) = model.expected_goals("Foo", "FC Test");
+        assert!(strong_home > weak_away);
+        assert!(strong_away > weak_home);
+    }
+}
@@ -0,0 +1,184 @@
+use crate::standings::fixtures;
+use crate::standings::tables::Table;
+
+// What the teams take with them into their section of the split.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Carryover {
+    // Every point carries over, like the Scottish Premiership.
+    Full,
+    // Points are halved and rounded up, like the Belgian Pro League.
+    Halved,
+}
+
+// A league that splits into a top and a bottom section after a number of rounds. Teams only
+// play the other teams of their section after the split and cannot move between sections.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SplitRules {
+    pub after_round: u32,
+    pub top_size: usize,
+    pub carryover: Carryover,
+    // How many times every pair in a section meets after the split, 1 or 2.
+    pub meetings: usize,
+}
+
+impl SplitRules {
+    // The sections after the split, top section first. Each section starts from the standings
+    // after `after_round` with the carryover applied, adds the later matches between its
+    // teams and schedules the rest of the section's round robin.
+    pub fn split(&self, table: &Table) -> Result<Vec<Table>, &'static str> {
+        if self.top_size < 2 || self.top_size + 2 > table.teams.len() {
+            return Err("Both sections of the split need at least two teams.");
+        }
+
+        let at_split = table.standings_after_round(self.after_round);
+        if at_split
+            .teams
+            .iter()
+            .any(|team| team.played < self.after_round)
+        {
+            return Err("The round of the split has not been played yet.");
+        }
+
+        let (top, bottom) = at_split.teams.split_at(self.top_size);
+        let sections = [("Championship round", top), ("Relegation round", bottom)]
+            .into_iter()
+            .map(|(section_name, section_teams)| {
+                let team_names: Vec<String> =
+                    section_teams.iter().map(|team| team.name.clone()).collect();
+                let mut teams = section_teams.to_vec();
+                if self.carryover == Carryover::Halved {
+                    for team in &mut teams {
+                        team.points_adjustment =
+                            team.points.div_ceil(2) as i32 - team.points as i32;
+                    }
+                }
+
+                let mut section = Table {
+                    name: format!("{} {}", table.name, section_name),
+                    teams,
+                    fixtures: if self.meetings > 1 {
+                        fixtures::double_round_robin(&team_names)
+                    } else {
+                        fixtures::single_round_robin(&team_names)
+                    },
+                    carried_over: true,
+                    ..Default::default()
+                };
+
+                table
+                    .matches
+                    .iter()
+                    .filter(|game| {
+                        game.round > self.after_round
+                            && team_names.contains(&game.home_team)
+                            && team_names.contains(&game.away_team)
+                    })
+                    .for_each(|game| section.add_match(game.clone()));
+                section.update_table();
+                section
+            })
+            .collect();
+
+        Ok(sections)
+    }
+}
+
+// Parses the split field of a competition line, "round/top size/carryover" optionally
+// followed by "/meetings", e.g. "33/6/full" or "30/6/halved/2".
+pub fn parse_split_rules(split_str: &str) -> Result<SplitRules, &'static str> {
+    let parts: Vec<&str> = split_str.split('/').map(|part| part.trim()).collect();
+    if parts.len() < 3 || parts.len() > 4 {
+        return Err("Invalid split format.");
+    }
+
+    let after_round = parts[0]
+        .parse::<u32>()
+        .map_err(|_| "Could not parse the round of the split.")?;
+    let top_size = parts[1]
+        .parse::<usize>()
+        .map_err(|_| "Could not parse the size of the top section.")?;
+    let carryover = match parts[2].to_lowercase().as_str() {
+        "full" => Carryover::Full,
+        "halved" => Carryover::Halved,
+        _ => return Err("Unknown points carryover, use full or halved."),
+    };
+    let meetings = match parts.get(3) {
+        Some(meetings) => match meetings.parse::<usize>() {
+            Ok(meetings @ 1..=2) => meetings,
+            _ => return Err("Teams meet once or twice after the split."),
+        },
+        None => 1,
+    };
+
+    Ok(SplitRules {
+        after_round,
+        top_size,
+        carryover,
+        meetings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standings::matches;
+    use crate::standings::tables;
+
+    // Four teams after one round: A and B won, C and D lost.
+    fn create_test_table() -> Table {
+        let team_names: Vec<String> = ["A", "B", "C", "D"]
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        let mut table = tables::create_table(&team_names);
+        table.name = String::from("Test");
+        table.add_match(matches::create_match("A", "C", 3, 0, 1));
+        table.add_match(matches::create_match("B", "D", 1, 0, 1));
+        table
+    }
+
+    #[test]
+    fn test_split_full_carryover() {
+        let mut table = create_test_table();
+        table.add_match(matches::create_match("A", "B", 0, 2, 2));
+        table.add_match(matches::create_match("C", "D", 1, 1, 2));
+        let rules = parse_split_rules("1/2/full").unwrap();
+
+        let sections = rules.split(&table).unwrap();
+        assert_eq!(sections[0].name, "Test Championship round");
+        assert_eq!(sections[0].teams[0].name, "B");
+        assert_eq!(sections[0].teams[0].points, 6);
+        assert_eq!(sections[0].teams[1].points, 3);
+        assert_eq!(sections[0].remaining_fixtures().unwrap().len(), 0);
+        assert_eq!(sections[1].teams[0].points, 1);
+        assert_eq!(sections[1].teams[0].played, 2);
+    }
+
+    #[test]
+    fn test_split_halved_points() {
+        let rules = parse_split_rules("1/2/halved/2").unwrap();
+
+        let sections = rules.split(&create_test_table()).unwrap();
+        assert_eq!(sections[0].teams[0].name, "A");
+        assert_eq!(sections[0].teams[0].points, 2);
+        assert_eq!(sections[0].teams[0].goal_difference, 3);
+        assert_eq!(sections[0].remaining_fixtures().unwrap().len(), 2);
+        assert_eq!(sections[1].teams[0].points, 0);
+    }
+
+    #[test]
+    fn test_split_before_round() {
+        let rules = parse_split_rules("2/2/full").unwrap();
+
+        assert_eq!(
+            rules.split(&create_test_table()).err(),
+            Some("The round of the split has not been played yet.")
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown points carryover, use full or halved.")]
+    fn test_parse_split_rules_panic_unknown_carryover() {
+        parse_split_rules("22/6/kept").unwrap();
+    }
+}
@@ -0,0 +1,271 @@
+use crate::standings::calendar;
+use crate::standings::matches::Match;
+use chrono::NaiveDate;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Position {
+    Goalkeeper,
+    Defender,
+    Midfielder,
+    Forward,
+}
+
+impl Position {
+    pub fn from_string(s: &str) -> Option<Position> {
+        match s.trim().to_lowercase().as_str() {
+            "gk" | "goalkeeper" => Some(Position::Goalkeeper),
+            "df" | "defender" => Some(Position::Defender),
+            "mf" | "midfielder" => Some(Position::Midfielder),
+            "fw" | "forward" => Some(Position::Forward),
+            _ => None,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Position::Goalkeeper => "GK",
+            Position::Defender => "DF",
+            Position::Midfielder => "MF",
+            Position::Forward => "FW",
+        }
+    }
+}
+
+// A squad registration. A player who changes clubs has one registration per club, limited
+// by the dates they joined and left.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Player {
+    pub name: String,
+    pub team: String,
+    pub shirt_number: u32,
+    pub position: Position,
+    pub date_of_birth: NaiveDate,
+    pub joined: Option<NaiveDate>,
+    pub left: Option<NaiveDate>,
+}
+
+impl Player {
+    // Whether the player is registered for the team on the date. Without a date any
+    // registration with the team counts.
+    pub fn plays_for(&self, team_name: &str, date: Option<NaiveDate>) -> bool {
+        self.team == team_name
+            && date.is_none_or(|date| {
+                self.joined.is_none_or(|joined| joined <= date)
+                    && self.left.is_none_or(|left| date <= left)
+            })
+    }
+
+    // Age in whole years on the date.
+    pub fn age_on(&self, date: NaiveDate) -> u32 {
+        date.years_since(self.date_of_birth).unwrap_or(0)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Squads {
+    pub players: Vec<Player>,
+}
+
+impl Squads {
+    pub fn add_player(&mut self, player: Player) {
+        self.players.push(player);
+    }
+
+    pub fn has_squad(&self, team_name: &str) -> bool {
+        self.players.iter().any(|player| player.team == team_name)
+    }
+
+    // The team's players on the date, by shirt number.
+    pub fn squad(&self, team_name: &str, date: Option<NaiveDate>) -> Vec<&Player> {
+        let mut squad: Vec<&Player> = self
+            .players
+            .iter()
+            .filter(|player| player.plays_for(team_name, date))
+            .collect();
+        squad.sort_by_key(|player| player.shirt_number);
+        squad
+    }
+
+    // Finds a player of the team on the date by name or by shirt number given as "#9".
+    pub fn find(
+        &self,
+        reference: &str,
+        team_name: &str,
+        date: Option<NaiveDate>,
+    ) -> Option<&Player> {
+        let reference = reference.trim();
+        let shirt_number = reference
+            .strip_prefix('#')
+            .and_then(|number| number.parse::<u32>().ok());
+
+        self.squad(team_name, date)
+            .into_iter()
+            .find(|player| match shirt_number {
+                Some(number) => player.shirt_number == number,
+                None => player.name == reference,
+            })
+    }
+
+    // Replaces shirt number references in the goal and card events with the player names and
+    // checks that every player plays for the right team on the match date. Teams without a
+    // registered squad are not checked.
+    pub fn resolve_events(&self, game: &mut Match) -> Result<(), &'static str> {
+        let date = game.date();
+
+        let mut scorers = Vec::new();
+        for goal in &game.goals {
+            let team = goal.scorer_team(game);
+            scorers.push(
+                self.resolve(&goal.scorer, team, date)
+                    .ok_or("A goal scorer is not in the squad of the team on the match date.")?,
+            );
+        }
+        let mut booked = Vec::new();
+        for card in &game.cards {
+            booked.push(
+                self.resolve(&card.player, card.team(game), date)
+                    .ok_or("A booked player is not in the squad of the team on the match date.")?,
+            );
+        }
+
+        for (goal, scorer) in game.goals.iter_mut().zip(scorers) {
+            goal.scorer = scorer;
+        }
+        for (card, player) in game.cards.iter_mut().zip(booked) {
+            card.player = player;
+        }
+        Ok(())
+    }
+
+    // The player name a reference stands for, the reference itself when the team has no
+    // registered squad.
+    fn resolve(&self, reference: &str, team_name: &str, date: Option<NaiveDate>) -> Option<String> {
+        if !self.has_squad(team_name) {
+            return Some(reference.to_string());
+        }
+        self.find(reference, team_name, date)
+            .map(|player| player.name.clone())
+    }
+}
+
+// Parses a squad line, "team;name;shirt number;position;date of birth" optionally followed by
+// the dates the player joined and left the team, e.g.
+// "Malmö FF;Anders Berg;9;FW;1995-03-12;2024-01-15".
+pub fn parse_player(player_str: &str) -> Result<Player, &'static str> {
+    let parts: Vec<&str> = player_str.split(';').map(|part| part.trim()).collect();
+    if parts.len() < 5 || parts.len() > 7 {
+        return Err("Player line does not contain five to seven parts.");
+    }
+    if parts[0].is_empty() || parts[1].is_empty() {
+        return Err("The team or player name is missing.");
+    }
+
+    let shirt_number = parts[2]
+        .parse::<u32>()
+        .map_err(|_| "Could not parse the shirt number.")?;
+    let position =
+        Position::from_string(parts[3]).ok_or("Unknown position, use GK, DF, MF or FW.")?;
+    let date_of_birth = calendar::parse_date(parts[4])?;
+    let optional_date = |index: usize| -> Result<Option<NaiveDate>, &'static str> {
+        match parts.get(index).filter(|part| !part.is_empty()) {
+            Some(date_str) => calendar::parse_date(date_str).map(Some),
+            None => Ok(None),
+        }
+    };
+
+    Ok(Player {
+        name: parts[1].to_string(),
+        team: parts[0].to_string(),
+        shirt_number,
+        position,
+        date_of_birth,
+        joined: optional_date(5)?,
+        left: optional_date(6)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standings::matches;
+    use crate::standings::scorers;
+
+    fn create_test_squads() -> Squads {
+        let mut squads = Squads::default();
+        [
+            "Malmö FF;Anders Berg;9;FW;1995-03-12;;2024-06-30",
+            "Malmö FF;Erik Nilsson;1;GK;1990-11-02",
+            "AIK;Anders Berg;19;FW;1995-03-12;2024-07-01",
+        ]
+        .iter()
+        .for_each(|line| squads.add_player(parse_player(line).unwrap()));
+        squads
+    }
+
+    fn create_test_match(goals_str: &str, date: &str) -> Match {
+        Match {
+            kick_off: calendar::parse_kick_off(date).ok(),
+            goals: scorers::parse_goals(goals_str, 1, 0).unwrap(),
+            ..matches::create_match("Malmö FF", "Kalmar FF", 1, 0, 1)
+        }
+    }
+
+    #[test]
+    fn test_parse_player() {
+        let player = parse_player("Malmö FF;Anders Berg;9;fw;1995-03-12;;2024-06-30").unwrap();
+
+        assert_eq!(player.position, Position::Forward);
+        assert_eq!(player.joined, None);
+        assert_eq!(player.left, NaiveDate::from_ymd_opt(2024, 6, 30));
+        assert_eq!(
+            player.age_on(NaiveDate::from_ymd_opt(2024, 3, 11).unwrap()),
+            28
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown position, use GK, DF, MF or FW.")]
+    fn test_parse_player_panic_unknown_position() {
+        parse_player("Malmö FF;Anders Berg;9;ST;1995-03-12").unwrap();
+    }
+
+    #[test]
+    fn test_find() {
+        let squads = create_test_squads();
+        let spring = NaiveDate::from_ymd_opt(2024, 4, 1);
+        let autumn = NaiveDate::from_ymd_opt(2024, 9, 1);
+
+        assert_eq!(
+            squads.find("#9", "Malmö FF", spring).unwrap().name,
+            "Anders Berg"
+        );
+        assert!(squads.find("Anders Berg", "Malmö FF", autumn).is_none());
+        assert_eq!(
+            squads
+                .find("Anders Berg", "AIK", autumn)
+                .unwrap()
+                .shirt_number,
+            19
+        );
+        assert_eq!(squads.squad("Malmö FF", autumn).len(), 1);
+    }
+
+    #[test]
+    fn test_resolve_events() {
+        let squads = create_test_squads();
+
+        let mut game = create_test_match("#9 34'", "2024-04-14 15:00");
+        squads.resolve_events(&mut game).unwrap();
+        assert_eq!(game.goals[0].scorer, "Anders Berg");
+
+        let mut after_transfer = create_test_match("Anders Berg 34'", "2024-08-14 19:00");
+        assert_eq!(
+            squads.resolve_events(&mut after_transfer),
+            Err("A goal scorer is not in the squad of the team on the match date.")
+        );
+
+        // Kalmar FF has no registered squad, so its scorers are not checked.
+        let mut own_goal = create_test_match("Someone 80' (og)", "2024-04-14 15:00");
+        assert!(squads.resolve_events(&mut own_goal).is_ok());
+    }
+}
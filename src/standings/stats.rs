@@ -0,0 +1,395 @@
+use std::fmt::Write;
+
+use crate::standings::matches::Match;
+use crate::standings::tables::Table;
+
+// Number of matches listed under biggest wins and highest-scoring games.
+pub const DEFAULT_TOP_MATCHES: usize = 5;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TeamSeasonStats {
+    pub name: String,
+    pub clean_sheets: usize,
+    pub failed_to_score: usize,
+    pub longest_winning_streak: usize,
+    pub longest_unbeaten_streak: usize,
+    pub longest_winless_streak: usize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SeasonStats {
+    pub matches_played: usize,
+    pub total_goals: i32,
+    pub home_wins: usize,
+    pub draws: usize,
+    pub away_wins: usize,
+    pub biggest_wins: Vec<Match>,
+    pub highest_scoring: Vec<Match>,
+    // In table order.
+    pub teams: Vec<TeamSeasonStats>,
+    // Final scores as (home goals, away goals) with their number of occurrences, most common
+    // first.
+    pub score_frequencies: Vec<((i32, i32), usize)>,
+}
+
+impl SeasonStats {
+    pub fn average_goals(&self) -> f64 {
+        if self.matches_played == 0 {
+            0.0
+        } else {
+            self.total_goals as f64 / self.matches_played as f64
+        }
+    }
+
+    // Share of the played matches, in percent.
+    pub fn percentage(&self, count: usize) -> f64 {
+        if self.matches_played == 0 {
+            0.0
+        } else {
+            100.0 * count as f64 / self.matches_played as f64
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+
+        writeln!(text, "Season statistics").unwrap();
+        writeln!(
+            text,
+            "Matches: {}, goals: {}, average {:.2} per game",
+            self.matches_played,
+            self.total_goals,
+            self.average_goals()
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "Home wins: {:.1}%, draws: {:.1}%, away wins: {:.1}%",
+            self.percentage(self.home_wins),
+            self.percentage(self.draws),
+            self.percentage(self.away_wins)
+        )
+        .unwrap();
+
+        writeln!(text, "\nBiggest wins").unwrap();
+        self.biggest_wins
+            .iter()
+            .for_each(|game| writeln!(text, "  {}", game).unwrap());
+
+        writeln!(text, "\nHighest-scoring games").unwrap();
+        self.highest_scoring
+            .iter()
+            .for_each(|game| writeln!(text, "  {}", game).unwrap());
+
+        let column_width: usize = self
+            .teams
+            .iter()
+            .map(|team| team.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(4);
+
+        writeln!(text, "\nTeams (longest streaks)").unwrap();
+        writeln!(
+            text,
+            "{:<width$} {:>3}  {:>3}  {:>4}  {:>8}  {:>7}",
+            "Club",
+            "CS",
+            "FTS",
+            "Wins",
+            "Unbeaten",
+            "Winless",
+            width = column_width
+        )
+        .unwrap();
+        for team in &self.teams {
+            writeln!(
+                text,
+                "{:<width$} {:>3}  {:>3}  {:>4}  {:>8}  {:>7}",
+                team.name,
+                team.clean_sheets,
+                team.failed_to_score,
+                team.longest_winning_streak,
+                team.longest_unbeaten_streak,
+                team.longest_winless_streak,
+                width = column_width
+            )
+            .unwrap();
+        }
+
+        writeln!(text, "\nScore frequencies").unwrap();
+        for ((home_score, away_score), count) in &self.score_frequencies {
+            writeln!(
+                text,
+                "  {}-{}: {} ({:.1}%)",
+                home_score,
+                away_score,
+                count,
+                self.percentage(*count)
+            )
+            .unwrap();
+        }
+
+        text
+    }
+
+    pub fn to_json(&self) -> String {
+        let teams: Vec<String> = self
+            .teams
+            .iter()
+            .map(|team| {
+                format!(
+                    "{{\"name\": {}, \"clean_sheets\": {}, \"failed_to_score\": {}, \"longest_winning_streak\": {}, \"longest_unbeaten_streak\": {}, \"longest_winless_streak\": {}}}",
+                    json_string(&team.name),
+                    team.clean_sheets,
+                    team.failed_to_score,
+                    team.longest_winning_streak,
+                    team.longest_unbeaten_streak,
+                    team.longest_winless_streak
+                )
+            })
+            .collect();
+        let scores: Vec<String> = self
+            .score_frequencies
+            .iter()
+            .map(|((home_score, away_score), count)| {
+                format!(
+                    "{{\"score\": \"{}-{}\", \"count\": {}}}",
+                    home_score, away_score, count
+                )
+            })
+            .collect();
+
+        let mut json = String::from("{\n");
+        writeln!(json, "  \"matches_played\": {},", self.matches_played).unwrap();
+        writeln!(json, "  \"total_goals\": {},", self.total_goals).unwrap();
+        writeln!(json, "  \"average_goals\": {:.3},", self.average_goals()).unwrap();
+        writeln!(json, "  \"home_wins\": {},", self.home_wins).unwrap();
+        writeln!(json, "  \"draws\": {},", self.draws).unwrap();
+        writeln!(json, "  \"away_wins\": {},", self.away_wins).unwrap();
+        writeln!(
+            json,
+            "  \"biggest_wins\": {},",
+            json_array(&json_matches(&self.biggest_wins))
+        )
+        .unwrap();
+        writeln!(
+            json,
+            "  \"highest_scoring\": {},",
+            json_array(&json_matches(&self.highest_scoring))
+        )
+        .unwrap();
+        writeln!(json, "  \"teams\": {},", json_array(&teams)).unwrap();
+        writeln!(json, "  \"score_frequencies\": {}", json_array(&scores)).unwrap();
+        json.push_str("}\n");
+        json
+    }
+}
+
+// Collects the statistics of every match in the table's match log that was played out.
+pub fn season_stats(table: &Table, top_matches: usize) -> SeasonStats {
+    let ordered: Vec<&Match> = table
+        .ordered_matches()
+        .into_iter()
+        .filter(|game| game.counts_in_statistics())
+        .collect();
+    let mut stats = SeasonStats {
+        matches_played: ordered.len(),
+        ..Default::default()
+    };
+
+    for game in &ordered {
+        stats.total_goals += game.home_score + game.away_score;
+        if game.home_score > game.away_score {
+            stats.home_wins += 1;
+        } else if game.home_score < game.away_score {
+            stats.away_wins += 1;
+        } else {
+            stats.draws += 1;
+        }
+
+        let score = (game.home_score, game.away_score);
+        match stats
+            .score_frequencies
+            .iter_mut()
+            .find(|(existing, _)| *existing == score)
+        {
+            Some((_, count)) => *count += 1,
+            None => stats.score_frequencies.push((score, 1)),
+        }
+    }
+    stats
+        .score_frequencies
+        .sort_by(|(score1, count1), (score2, count2)| count2.cmp(count1).then(score1.cmp(score2)));
+
+    let mut wins: Vec<&Match> = ordered
+        .iter()
+        .copied()
+        .filter(|game| game.home_score != game.away_score)
+        .collect();
+    wins.sort_by_key(|game| {
+        let margin = (game.home_score - game.away_score).abs();
+        let winner_goals = game.home_score.max(game.away_score);
+        (-margin, -winner_goals)
+    });
+    stats.biggest_wins = wins.into_iter().take(top_matches).cloned().collect();
+
+    let mut high_scoring: Vec<&Match> = ordered.clone();
+    high_scoring.sort_by_key(|game| -(game.home_score + game.away_score));
+    stats.highest_scoring = high_scoring
+        .into_iter()
+        .take(top_matches)
+        .cloned()
+        .collect();
+
+    stats.teams = table
+        .teams
+        .iter()
+        .map(|team| team_season_stats(&team.name, &ordered))
+        .collect();
+
+    stats
+}
+
+fn team_season_stats(team_name: &str, ordered: &[&Match]) -> TeamSeasonStats {
+    let mut stats = TeamSeasonStats {
+        name: team_name.to_string(),
+        ..Default::default()
+    };
+    let (mut winning, mut unbeaten, mut winless) = (0, 0, 0);
+
+    for (scored, conceded) in ordered.iter().filter_map(|game| game.score_for(team_name)) {
+        if conceded == 0 {
+            stats.clean_sheets += 1;
+        }
+        if scored == 0 {
+            stats.failed_to_score += 1;
+        }
+
+        winning = if scored > conceded { winning + 1 } else { 0 };
+        unbeaten = if scored >= conceded { unbeaten + 1 } else { 0 };
+        winless = if scored <= conceded { winless + 1 } else { 0 };
+        stats.longest_winning_streak = stats.longest_winning_streak.max(winning);
+        stats.longest_unbeaten_streak = stats.longest_unbeaten_streak.max(unbeaten);
+        stats.longest_winless_streak = stats.longest_winless_streak.max(winless);
+    }
+
+    stats
+}
+
+fn json_matches(games: &[Match]) -> Vec<String> {
+    games
+        .iter()
+        .map(|game| {
+            let kick_off = match game.kick_off {
+                Some(kick_off) => json_string(&kick_off.format("%Y-%m-%d %H:%M").to_string()),
+                None => String::from("null"),
+            };
+            format!(
+                "{{\"round\": {}, \"kick_off\": {}, \"home_team\": {}, \"away_team\": {}, \"home_score\": {}, \"away_score\": {}}}",
+                game.round,
+                kick_off,
+                json_string(&game.home_team),
+                json_string(&game.away_team),
+                game.home_score,
+                game.away_score
+            )
+        })
+        .collect()
+}
+
+fn json_array(values: &[String]) -> String {
+    if values.is_empty() {
+        String::from("[]")
+    } else {
+        format!("[\n    {}\n  ]", values.join(",\n    "))
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if (c as u32) < 0x20 => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standings::tables;
+
+    fn create_test_table() -> Table {
+        let team_names = vec![
+            String::from("FC Test"),
+            String::from("Foo"),
+            String::from("Bar"),
+        ];
+
+        let mut test_table = tables::create_table(&team_names);
+        test_table.add_game("FC Test", "Foo", 2, 0);
+        test_table.add_game("Bar", "FC Test", 1, 1);
+        test_table.add_game("Foo", "Bar", 0, 4);
+        test_table.add_game("Foo", "FC Test", 3, 2);
+        test_table.add_game("FC Test", "Bar", 1, 1);
+        test_table.update_table();
+        test_table
+    }
+
+    #[test]
+    fn test_season_stats() {
+        let stats = season_stats(&create_test_table(), 2);
+
+        assert_eq!(stats.matches_played, 5);
+        assert_eq!(stats.total_goals, 15);
+        assert_eq!(stats.average_goals(), 3.0);
+        assert_eq!((stats.home_wins, stats.draws, stats.away_wins), (2, 2, 1));
+        assert_eq!(stats.percentage(stats.draws), 40.0);
+        assert_eq!(stats.score_frequencies[0], ((1, 1), 2));
+
+        assert_eq!(stats.biggest_wins.len(), 2);
+        assert_eq!(stats.biggest_wins[0].home_team, "Foo");
+        assert_eq!(stats.biggest_wins[0].away_score, 4);
+        assert_eq!(stats.highest_scoring[0].home_score, 3);
+    }
+
+    #[test]
+    fn test_team_season_stats() {
+        let stats = season_stats(&create_test_table(), DEFAULT_TOP_MATCHES);
+        let fc_test = stats
+            .teams
+            .iter()
+            .find(|team| team.name == "FC Test")
+            .unwrap();
+        let foo = stats.teams.iter().find(|team| team.name == "Foo").unwrap();
+
+        // FC Test: W D L D.
+        assert_eq!(fc_test.clean_sheets, 1);
+        assert_eq!(fc_test.failed_to_score, 0);
+        assert_eq!(fc_test.longest_winning_streak, 1);
+        assert_eq!(fc_test.longest_unbeaten_streak, 2);
+        assert_eq!(fc_test.longest_winless_streak, 3);
+
+        // Foo: L L W.
+        assert_eq!(foo.failed_to_score, 2);
+        assert_eq!(foo.longest_winless_streak, 2);
+    }
+
+    #[test]
+    fn test_to_json() {
+        let json = season_stats(&create_test_table(), 1).to_json();
+
+        assert!(json.starts_with("{\n  \"matches_played\": 5,"));
+        assert!(json.contains("\"average_goals\": 3.000,"));
+        assert!(json.contains("{\"score\": \"1-1\", \"count\": 2}"));
+        assert!(json.contains("\"home_team\": \"Foo\", \"away_team\": \"Bar\""));
+        assert_eq!(json_string("Say \"hi\"\n"), "\"Say \\\"hi\\\"\\n\"");
+    }
+}
@@ -1,12 +1,102 @@
+use chrono::{NaiveDate, NaiveDateTime, Weekday};
+use std::cmp::Ordering;
+
+use crate::standings::clinch;
+use crate::standings::elo::Ratings;
+use crate::standings::fixtures;
+use crate::standings::fixtures::Fixture;
+use crate::standings::matches;
+use crate::standings::matches::{Match, Venue};
 use crate::standings::team;
 use crate::standings::team::TeamStats;
+use crate::standings::zones::Zones;
+
+// Number of matches shown in the form column of `Table::print_standings`.
+pub const DEFAULT_FORM_LENGTH: usize = 5;
+
+const UNKNOWN_SCHEDULE: &str = "The remaining schedule is unknown without a full match log.";
 
+#[derive(Clone, Debug, Default)]
 pub struct Table {
+    // Competition or division name shown above the table.
+    pub name: String,
     pub teams: Vec<TeamStats>,
+    pub matches: Vec<Match>,
+    // The season schedule. When empty every team is expected to meet every other team home
+    // and away.
+    pub fixtures: Vec<Fixture>,
+    // Elo ratings, updated with every match added to the table.
+    pub ratings: Ratings,
+    pub zones: Zones,
+    // Set for a table whose records carry over games from before its match log, as a section
+    // of a split. Its fixtures only hold the games from the log on, so the remaining schedule
+    // is known without a full match log.
+    pub carried_over: bool,
 }
 impl Table {
     pub fn print(&mut self) {
-        println!("--------- Allsvenskan ------------");
+        self.print_with_columns(&[]);
+    }
+
+    // Prints the division table with the zone of every position, games in hand, clinch flags
+    // and form. Derived tables such as venue tables or split sections use `print`, since the
+    // zones and the schedule of the division do not apply to them.
+    pub fn print_standings(&mut self) {
+        // Without a known remaining schedule nothing is clinched or ruled out yet.
+        let flags = match clinch::position_ranges(self) {
+            Ok(ranges) => clinch::clinch_flags(&ranges, &self.zones),
+            Err(_) => Vec::new(),
+        };
+        let has_flags = flags.iter().any(|flag| !flag.is_empty());
+        let team_count = self.teams.len();
+        let zone_labels: Vec<String> = (1..=team_count)
+            .map(|position| self.zones.label(position, team_count).to_string())
+            .collect();
+        let mut columns = vec![("Zone", zone_labels)];
+
+        let games_in_hand: Vec<usize> = self
+            .teams
+            .iter()
+            .map(|team| self.games_in_hand(&team.name))
+            .collect();
+        if games_in_hand.iter().any(|games| *games > 0) {
+            let column = games_in_hand
+                .iter()
+                .map(|games| match games {
+                    0 => String::new(),
+                    games => format!("+{}", games),
+                })
+                .collect();
+            columns.push(("GiH", column));
+        }
+
+        if has_flags {
+            columns.push(("", flags));
+        }
+
+        if !self.matches.is_empty() {
+            let form: Vec<String> = self
+                .teams
+                .iter()
+                .map(|team| self.form(&team.name, DEFAULT_FORM_LENGTH, None))
+                .collect();
+            columns.push(("Form", form));
+        }
+
+        self.print_with_columns(&columns);
+
+        if has_flags {
+            println!("C: champions, E: European spot, S: safe, R: relegated");
+        }
+        if games_in_hand.iter().any(|games| *games > 0) {
+            println!("GiH: games in hand");
+        }
+    }
+
+    // Prints the table with additional columns appended to every row. Each column is a
+    // header and one value per team, in the same order as `self.teams`.
+    pub fn print_with_columns(&mut self, extra_columns: &[(&str, Vec<String>)]) {
+        println!("--------- {} ------------", self.name);
 
         let column_width: usize = self
             .teams
@@ -15,8 +105,26 @@ impl Table {
             .max()
             .unwrap_or(0);
 
+        let extra_widths: Vec<usize> = extra_columns
+            .iter()
+            .map(|(header, values)| {
+                values
+                    .iter()
+                    .map(|value| value.chars().count())
+                    .chain(std::iter::once(header.chars().count()))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let extra_header: String = extra_columns
+            .iter()
+            .zip(&extra_widths)
+            .map(|((header, _), width)| format!("  {:<width$}", header, width = width))
+            .collect();
+
         println!(
-            "{:<width1$} {:<width2$}  {:<width2$}  {:<width2$}  {:<width2$}  {:<width2$}  {:<width2$}  {:<width3$}  {:<width2$}",
+            "{:<width1$} {:<width2$}  {:<width2$}  {:<width2$}  {:<width2$}  {:<width2$}  {:<width2$}  {:<width3$}  {:<width2$}{}",
             "Club",
             "G",
             "W",
@@ -26,14 +134,24 @@ impl Table {
             "GC",
             "GS",
             "P",
+            extra_header,
             width1 = column_width,
             width2 = 2,
             width3 = 3
         );
 
-        for team in &mut self.teams {
+        for (i, team) in self.teams.iter().enumerate() {
+            let extra_values: String = extra_columns
+                .iter()
+                .zip(&extra_widths)
+                .map(|((_, values), width)| {
+                    let value = values.get(i).map(|value| value.as_str()).unwrap_or("");
+                    format!("  {:<width$}", value, width = width)
+                })
+                .collect();
+
             println!(
-                "{:<width1$} {:<width2$}  {:<width2$}  {:<width2$}  {:<width2$}  {:<width2$}  {:<width2$}  {:<width3$}  {:<width2$}",
+                "{:<width1$} {:<width2$}  {:<width2$}  {:<width2$}  {:<width2$}  {:<width2$}  {:<width2$}  {:<width3$}  {:<width2$}{}",
                 team.name.clone(),
                 &team.played,
                 &team.wins,
@@ -43,6 +161,7 @@ impl Table {
                 &team.goal_against,
                 &team.goal_difference,
                 &team.points,
+                extra_values,
                 width1 = column_width,
                 width2 = 2,
                 width3 = 3
@@ -50,60 +169,405 @@ impl Table {
         }
     }
 
+    // Prints the standings after the given round together with each team's movement
+    // compared to the round before.
+    pub fn print_round(&self, round: u32) {
+        let mut current = self.standings_after_round(round);
+        let previous = self.standings_after_round(round.saturating_sub(1));
+        let movement = current.movement_since(&previous);
+
+        println!("Standings after round {}", round);
+        current.print_with_columns(&[("+/-", movement)]);
+    }
+
     pub fn update_table(&mut self) {
         for team in &mut self.teams {
             team.calc_stats();
         }
 
-        self.teams.sort_by(|team1, team2| {
-            if team1.points != team2.points {
-                team2.points.cmp(&team1.points)
-            } else {
-                team2.goal_difference.cmp(&team1.goal_difference)
-            }
-        });
+        self.teams.sort_by(compare_teams);
     }
 
     pub fn add_game(&mut self, home_team: &str, away_team: &str, home_score: i32, away_score: i32) {
-        {
-            let result: Option<&mut TeamStats> =
-                self.teams.iter_mut().find(|team| team.name == home_team);
-
-            match result {
-                Some(home_team) => {
-                    if home_score > away_score {
-                        home_team.add_win(home_score, away_score);
-                    } else if home_score < away_score {
-                        home_team.add_defeat(home_score, away_score);
-                    } else {
-                        home_team.add_draw(home_score, away_score);
-                    }
-                }
-                None => {
-                    println!("Home team not found...");
-                    return;
+        let round = self.next_round(home_team, away_team);
+        self.add_match(matches::create_match(
+            home_team, away_team, home_score, away_score, round,
+        ));
+    }
+
+    pub fn add_match(&mut self, game: Match) {
+        if !self.teams.iter().any(|team| team.name == game.home_team) {
+            println!("Home team not found...");
+            return;
+        }
+        if !self.teams.iter().any(|team| team.name == game.away_team) {
+            println!("Away team not found...");
+            return;
+        }
+
+        if game.counts_in_table() {
+            for team in &mut self.teams {
+                if team.name == game.home_team {
+                    team.add_venue_result(Venue::Home, game.home_score, game.away_score);
+                } else if team.name == game.away_team {
+                    team.add_venue_result(Venue::Away, game.away_score, game.home_score);
                 }
             }
         }
-        {
-            let result: Option<&mut TeamStats> =
-                self.teams.iter_mut().find(|team| team.name == away_team);
-            match result {
-                Some(away_team) => {
-                    if away_score > home_score {
-                        away_team.add_win(away_score, home_score);
-                    } else if away_score < home_score {
-                        away_team.add_defeat(away_score, home_score);
-                    } else {
-                        away_team.add_draw(away_score, home_score);
-                    }
+
+        if game.counts_in_statistics() {
+            self.ratings.update(&game);
+        }
+        self.matches.push(game);
+    }
+
+    // The round a result belongs to when none is given: one after the latest round
+    // either team has played in.
+    pub fn next_round(&self, home_team: &str, away_team: &str) -> u32 {
+        self.matches
+            .iter()
+            .filter(|game| game.involves(home_team) || game.involves(away_team))
+            .map(|game| game.round)
+            .max()
+            .unwrap_or(0)
+            + 1
+    }
+
+    pub fn last_round(&self) -> u32 {
+        self.matches
+            .iter()
+            .map(|game| game.round)
+            .max()
+            .unwrap_or(0)
+    }
+
+    // Whether the match log holds every game of the team records. A table read from a table
+    // file has records but no matches behind them, so nothing can be rebuilt from its log.
+    pub fn has_complete_match_log(&self) -> bool {
+        self.teams.iter().all(|team| {
+            let logged = self
+                .matches
+                .iter()
+                .filter(|game| game.counts_in_table() && game.involves(&team.name))
+                .count();
+            (team.wins + team.draws + team.defeats) as usize == logged
+        })
+    }
+
+    // Rebuilds the standings from the match log, counting only matches played up to and
+    // including the given round.
+    pub fn standings_after_round(&self, round: u32) -> Table {
+        self.standings_from_matches(|game| game.round <= round)
+    }
+
+    // Rebuilds the standings from the matches played on or before the given date. Matches
+    // without a kick-off date are left out.
+    pub fn standings_on(&self, date: NaiveDate) -> Table {
+        self.standings_from_matches(|game| game.date().is_some_and(|played| played <= date))
+    }
+
+    fn standings_from_matches<F>(&self, include: F) -> Table
+    where
+        F: Fn(&Match) -> bool,
+    {
+        let team_names: Vec<String> = self.teams.iter().map(|team| team.name.clone()).collect();
+        let mut table = create_table(&team_names);
+        table.name = self.name.clone();
+        table.fixtures = self.fixtures.clone();
+        table.zones = self.zones.clone();
+
+        self.matches
+            .iter()
+            .filter(|game| include(game))
+            .for_each(|game| table.add_match(game.clone()));
+
+        table.update_table();
+        table
+    }
+
+    // Standings from the home or away records of the teams. The table has no match log, so
+    // it is printed without a form column.
+    pub fn venue_table(&self, venue: Venue) -> Table {
+        let teams = self
+            .teams
+            .iter()
+            .map(|team| {
+                let record = team.venue_record(venue);
+                TeamStats {
+                    name: team.name.clone(),
+                    wins: record.wins,
+                    draws: record.draws,
+                    defeats: record.defeats,
+                    goal_scored: record.goal_scored,
+                    goal_against: record.goal_against,
+                    ..Default::default()
                 }
-                None => {
-                    println!("Away team not found...");
-                    return;
+            })
+            .collect();
+        let mut table = Table {
+            name: self.name.clone(),
+            teams,
+            ..Default::default()
+        };
+
+        table.update_table();
+        table
+    }
+
+    // Whether some games of the team records are missing from their home and away records,
+    // as for records read from a table file.
+    pub fn has_unsplit_records(&self) -> bool {
+        self.teams.iter().any(|team| {
+            team.home.played() + team.away.played() != team.wins + team.draws + team.defeats
+        })
+    }
+
+    // Marks the remaining fixture between the teams as postponed. Without a schedule the
+    // default double round robin becomes the schedule. Without a full match log it is unknown
+    // which fixtures were played, so nothing can be postponed.
+    pub fn postpone(
+        &mut self,
+        home_team: &str,
+        away_team: &str,
+        reason: &str,
+    ) -> Result<(), &'static str> {
+        if !self.has_known_schedule() {
+            return Err(UNKNOWN_SCHEDULE);
+        }
+        if self.fixtures.is_empty() {
+            self.fixtures = self.default_schedule();
+        }
+        let fixture = self.open_fixture(home_team, away_team)?;
+        if fixture.awaits_new_date() {
+            return Err("The fixture is already postponed.");
+        }
+
+        let original_kick_off = fixture.kick_off.take();
+        fixture.postponement = Some(fixtures::Postponement {
+            reason: reason.to_string(),
+            original_kick_off,
+        });
+        Ok(())
+    }
+
+    // Gives a postponed fixture its new kick-off.
+    pub fn reschedule(
+        &mut self,
+        home_team: &str,
+        away_team: &str,
+        kick_off: NaiveDateTime,
+    ) -> Result<(), &'static str> {
+        let fixture = self.open_fixture(home_team, away_team)?;
+        if fixture.postponement.is_none() {
+            return Err("The fixture has not been postponed.");
+        }
+
+        fixture.kick_off = Some(kick_off);
+        Ok(())
+    }
+
+    // Matches a team has not played although the rest of the league has moved past them:
+    // remaining fixtures that were postponed or belong to a round already played. None are
+    // counted while the remaining schedule is unknown.
+    pub fn games_in_hand(&self, team_name: &str) -> usize {
+        let Ok(remaining) = self.remaining_fixtures() else {
+            return 0;
+        };
+        let last_round = self.last_round();
+        remaining
+            .iter()
+            .filter(|fixture| fixture.involves(team_name))
+            .filter(|fixture| {
+                fixture.postponement.is_some()
+                    || fixture.round.is_some_and(|round| round <= last_round)
+            })
+            .count()
+    }
+
+    // The schedule entry of a fixture between the teams that has not been played yet.
+    fn open_fixture(
+        &mut self,
+        home_team: &str,
+        away_team: &str,
+    ) -> Result<&mut Fixture, &'static str> {
+        let played = self
+            .matches
+            .iter()
+            .filter(|game| game.home_team == home_team && game.away_team == away_team)
+            .filter(|game| game.result_type != matches::ResultType::Abandoned)
+            .count();
+        self.fixtures
+            .iter_mut()
+            .filter(|fixture| fixture.home_team == home_team && fixture.away_team == away_team)
+            .nth(played)
+            .ok_or("The fixture is not in the remaining schedule.")
+    }
+
+    fn default_schedule(&self) -> Vec<Fixture> {
+        let team_names: Vec<String> = self.teams.iter().map(|team| team.name.clone()).collect();
+        fixtures::double_round_robin(&team_names)
+    }
+
+    // Whether the remaining fixtures are known. The schedule, or without one the default
+    // double round robin, only tells which games are left when the match log holds every game
+    // of the records, or when the records were carried over (see `carried_over`).
+    pub fn has_known_schedule(&self) -> bool {
+        (self.carried_over && !self.fixtures.is_empty()) || self.has_complete_match_log()
+    }
+
+    // The number of games the team has left. When the schedule is unknown it is worked out
+    // from the games played, every team meeting every other team home and away.
+    pub fn remaining_games(&self, team_name: &str) -> usize {
+        if let Ok(remaining) = self.remaining_fixtures() {
+            return remaining
+                .iter()
+                .filter(|fixture| fixture.involves(team_name))
+                .count();
+        }
+
+        let played = self
+            .teams
+            .iter()
+            .find(|team| team.name == team_name)
+            .map_or(0, |team| team.wins + team.draws + team.defeats);
+        (2 * self.teams.len().saturating_sub(1)).saturating_sub(played as usize)
+    }
+
+    // Fixtures of the schedule that have not been played yet. Without a schedule they are the
+    // default double round robin minus the match log. A table read from a table file has
+    // records the log does not hold, so its remaining fixtures are unknown even with a
+    // schedule, see `has_known_schedule`.
+    pub fn remaining_fixtures(&self) -> Result<Vec<Fixture>, &'static str> {
+        if !self.has_known_schedule() {
+            return Err(UNKNOWN_SCHEDULE);
+        }
+        if self.fixtures.is_empty() {
+            Ok(fixtures::remaining_fixtures(
+                &self.default_schedule(),
+                &self.matches,
+            ))
+        } else {
+            Ok(fixtures::remaining_fixtures(&self.fixtures, &self.matches))
+        }
+    }
+
+    // The matches that count in the table in the order they were played: by round, then by
+    // kick-off.
+    pub fn ordered_matches(&self) -> Vec<&Match> {
+        let mut ordered: Vec<&Match> = self
+            .matches
+            .iter()
+            .filter(|game| game.counts_in_table())
+            .collect();
+        ordered.sort_by_key(|game| (game.round, game.kick_off));
+        ordered
+    }
+
+    // The team's latest matches, oldest first, optionally only those played at one venue.
+    pub fn last_matches(&self, team_name: &str, count: usize, venue: Option<Venue>) -> Vec<&Match> {
+        let team_matches: Vec<&Match> = self
+            .ordered_matches()
+            .into_iter()
+            .filter(|game| match venue {
+                Some(venue) => game.side(venue).0 == team_name,
+                None => game.involves(team_name),
+            })
+            .collect();
+
+        let skip = team_matches.len().saturating_sub(count);
+        team_matches.into_iter().skip(skip).collect()
+    }
+
+    // Form guide such as "WWDLW", oldest result first.
+    pub fn form(&self, team_name: &str, count: usize, venue: Option<Venue>) -> String {
+        self.last_matches(team_name, count, venue)
+            .iter()
+            .filter_map(|game| game.score_for(team_name))
+            .map(|(scored, conceded)| {
+                if scored > conceded {
+                    'W'
+                } else if scored < conceded {
+                    'L'
+                } else {
+                    'D'
                 }
-            }
+            })
+            .collect()
+    }
+
+    // Standings over each team's last `count` matches only. The table has no match log, since a
+    // match may be among the last matches of one team but not of its opponent.
+    pub fn form_table(&self, count: usize, venue: Option<Venue>) -> Table {
+        let team_names: Vec<String> = self.teams.iter().map(|team| team.name.clone()).collect();
+        let mut table = create_table(&team_names);
+        table.name = self.name.clone();
+
+        for team in &mut table.teams {
+            let scores: Vec<(i32, i32)> = self
+                .last_matches(&team.name, count, venue)
+                .iter()
+                .filter_map(|game| game.score_for(&team.name))
+                .collect();
+            scores
+                .into_iter()
+                .for_each(|(scored, conceded)| team.add_result(scored, conceded));
         }
+
+        table.update_table();
+        table
+    }
+
+    // Matches with a kick-off between the two dates (inclusive), optionally only those played
+    // on a given weekday, ordered by kick-off.
+    pub fn matches_between(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        weekday: Option<Weekday>,
+    ) -> Vec<&Match> {
+        let mut found: Vec<&Match> = self
+            .matches
+            .iter()
+            .filter(|game| game.date().is_some_and(|date| date >= from && date <= to))
+            .filter(|game| weekday.is_none() || game.weekday() == weekday)
+            .collect();
+
+        found.sort_by_key(|game| game.kick_off);
+        found
+    }
+
+    pub fn position(&self, team_name: &str) -> Option<usize> {
+        self.teams
+            .iter()
+            .position(|team| team.name == team_name)
+            .map(|index| index + 1)
+    }
+
+    // Position changes compared to an earlier table, e.g. "▲2", "▼1" or "-", in the order
+    // of `self.teams`.
+    pub fn movement_since(&self, previous: &Table) -> Vec<String> {
+        self.teams
+            .iter()
+            .enumerate()
+            .map(|(i, team)| match previous.position(&team.name) {
+                Some(old_position) if old_position > i + 1 => {
+                    format!("▲{}", old_position - (i + 1))
+                }
+                Some(old_position) if old_position < i + 1 => {
+                    format!("▼{}", (i + 1) - old_position)
+                }
+                _ => String::from("-"),
+            })
+            .collect()
+    }
+}
+
+// Table order: points, then goal difference.
+pub fn compare_teams(team1: &TeamStats, team2: &TeamStats) -> Ordering {
+    if team1.points != team2.points {
+        team2.points.cmp(&team1.points)
+    } else {
+        team2.goal_difference.cmp(&team1.goal_difference)
     }
 }
 
@@ -120,12 +584,14 @@ pub fn create_table(teams: &[String]) -> Table {
 
     Table {
         teams: current_teams,
+        ..Default::default()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::standings::calendar;
 
     #[test]
     fn test_create_table() {
@@ -233,4 +699,333 @@ mod tests {
         assert_eq!(test_table.teams[2].points, 0);
         assert_eq!(test_table.teams[2].name, home_team); // Team FC test should now be placed last since they have worse goal summary than Bar.
     }
+
+    #[test]
+    fn test_add_game_records_match() {
+        let team_names = vec![
+            String::from("FC Test"),
+            String::from("Foo"),
+            String::from("Bar"),
+        ];
+
+        let mut test_table = create_table(&team_names);
+        test_table.add_game("FC Test", "Foo", 1, 0);
+        test_table.add_game("Bar", "FC Test", 2, 2);
+        test_table.add_game("Foo", "Unknown", 2, 2);
+
+        assert_eq!(test_table.matches.len(), 2);
+        assert_eq!(test_table.matches[0].round, 1);
+        assert_eq!(test_table.matches[1].round, 2); // FC Test has already played round 1.
+    }
+
+    #[test]
+    fn test_standings_after_round() {
+        let team_names = vec![
+            String::from("FC Test"),
+            String::from("Foo"),
+            String::from("Bar"),
+            String::from("Baz"),
+        ];
+
+        let mut test_table = create_table(&team_names);
+        test_table.add_match(matches::create_match("FC Test", "Foo", 3, 0, 1));
+        test_table.add_match(matches::create_match("Bar", "Baz", 1, 1, 1));
+        test_table.add_match(matches::create_match("Foo", "Bar", 2, 0, 2));
+        test_table.add_match(matches::create_match("Baz", "FC Test", 4, 0, 2));
+
+        let after_first = test_table.standings_after_round(1);
+        assert_eq!(after_first.teams[0].name, "FC Test");
+        assert_eq!(after_first.teams[0].played, 1);
+        assert_eq!(after_first.teams[3].name, "Foo");
+        assert_eq!(after_first.matches.len(), 2);
+
+        let after_second = test_table.standings_after_round(2);
+        assert_eq!(after_second.teams[0].name, "Baz");
+        assert_eq!(after_second.teams[0].points, 4);
+        assert_eq!(test_table.last_round(), 2);
+        assert!(test_table.has_complete_match_log());
+
+        let movement = after_second.movement_since(&after_first);
+        assert_eq!(movement[0], "▲2"); // Baz went from 3rd to 1st.
+        assert_eq!(after_second.position("FC Test"), Some(2));
+        assert_eq!(movement[1], "▼1");
+
+        // Records read from a table file have no matches behind them.
+        test_table.teams[0].wins += 1;
+        assert!(!test_table.has_complete_match_log());
+    }
+
+    #[test]
+    fn test_standings_on_date_and_matches_between() {
+        let team_names = vec![
+            String::from("FC Test"),
+            String::from("Foo"),
+            String::from("Bar"),
+        ];
+
+        let dated_match = |home: &str, away: &str, home_score, away_score, kick_off: &str| Match {
+            kick_off: Some(calendar::parse_kick_off(kick_off).unwrap()),
+            ..matches::create_match(home, away, home_score, away_score, 1)
+        };
+
+        let mut test_table = create_table(&team_names);
+        test_table.add_match(dated_match("Foo", "Bar", 0, 1, "2024-04-07 17:30"));
+        test_table.add_match(dated_match("FC Test", "Foo", 2, 0, "2024-04-06 15:00"));
+        test_table.add_match(dated_match("Bar", "FC Test", 1, 1, "2024-04-13 15:00"));
+        test_table.add_game("Foo", "FC Test", 3, 0);
+
+        let table_on_date = test_table.standings_on(NaiveDate::from_ymd_opt(2024, 4, 7).unwrap());
+        assert_eq!(table_on_date.matches.len(), 2);
+        assert_eq!(table_on_date.teams[2].name, "Foo");
+        assert_eq!(table_on_date.teams[2].played, 2);
+
+        let from = NaiveDate::from_ymd_opt(2024, 4, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2024, 4, 30).unwrap();
+        let in_april = test_table.matches_between(from, to, None);
+        assert_eq!(in_april.len(), 3);
+        assert_eq!(in_april[0].home_team, "FC Test"); // Ordered by kick-off.
+
+        let on_saturdays = test_table.matches_between(from, to, Some(Weekday::Sat));
+        assert_eq!(on_saturdays.len(), 2);
+    }
+
+    #[test]
+    fn test_venue_table() {
+        let team_names = vec![
+            String::from("FC Test"),
+            String::from("Foo"),
+            String::from("Bar"),
+        ];
+
+        let mut test_table = create_table(&team_names);
+        test_table.add_game("FC Test", "Foo", 2, 0);
+        test_table.add_game("Bar", "FC Test", 3, 1);
+        test_table.add_game("Foo", "Bar", 1, 1);
+
+        let home_table = test_table.venue_table(Venue::Home);
+        assert_eq!(home_table.teams[0].name, "FC Test");
+        assert_eq!(home_table.teams[0].played, 1);
+        assert_eq!(home_table.teams[0].points, 3);
+        assert_eq!(home_table.position("Foo"), Some(3));
+
+        let away_table = test_table.venue_table(Venue::Away);
+        assert_eq!(away_table.teams[0].name, "Bar");
+        assert_eq!(away_table.teams[0].points, 1);
+        assert_eq!(away_table.teams[2].name, "Foo");
+        assert_eq!(away_table.teams[2].goal_against, 2);
+        assert!(away_table.matches.is_empty());
+        assert!(!test_table.has_unsplit_records());
+
+        // Records read from a table file are not split by venue.
+        test_table.teams[0].wins += 1;
+        assert!(test_table.has_unsplit_records());
+        assert_eq!(test_table.venue_table(Venue::Home).teams[0].points, 3);
+    }
+
+    #[test]
+    fn test_form() {
+        let team_names = vec![
+            String::from("FC Test"),
+            String::from("Foo"),
+            String::from("Bar"),
+        ];
+
+        let mut test_table = create_table(&team_names);
+        test_table.add_match(matches::create_match("Foo", "FC Test", 0, 0, 3));
+        test_table.add_match(matches::create_match("FC Test", "Foo", 2, 0, 1));
+        test_table.add_match(matches::create_match("Bar", "FC Test", 3, 1, 2));
+        test_table.add_match(matches::create_match("FC Test", "Bar", 1, 0, 4));
+
+        assert_eq!(test_table.form("FC Test", 5, None), "WLDW");
+        assert_eq!(test_table.form("FC Test", 2, None), "DW");
+        assert_eq!(test_table.form("FC Test", 5, Some(Venue::Home)), "WW");
+        assert_eq!(test_table.form("FC Test", 5, Some(Venue::Away)), "LD");
+        assert_eq!(test_table.form("Unknown", 5, None), "");
+    }
+
+    #[test]
+    fn test_form_table() {
+        let team_names = vec![
+            String::from("FC Test"),
+            String::from("Foo"),
+            String::from("Bar"),
+        ];
+
+        let mut test_table = create_table(&team_names);
+        test_table.add_match(matches::create_match("FC Test", "Foo", 5, 0, 1));
+        test_table.add_match(matches::create_match("FC Test", "Bar", 4, 0, 2));
+        test_table.add_match(matches::create_match("Foo", "Bar", 1, 0, 3));
+        test_table.add_match(matches::create_match("Bar", "FC Test", 1, 0, 4));
+        test_table.add_match(matches::create_match("Foo", "FC Test", 2, 0, 5));
+
+        let form_table = test_table.form_table(2, None);
+        assert_eq!(form_table.teams[0].name, "Foo");
+        assert_eq!(form_table.teams[0].points, 6);
+        assert_eq!(form_table.teams[2].name, "FC Test");
+        assert_eq!(form_table.teams[2].played, 2);
+        assert_eq!(form_table.teams[2].points, 0);
+        assert!(form_table.matches.is_empty());
+    }
+
+    #[test]
+    fn test_add_game_updates_ratings() {
+        let team_names = vec![
+            String::from("FC Test"),
+            String::from("Foo"),
+            String::from("Bar"),
+        ];
+
+        let mut test_table = create_table(&team_names);
+        test_table.add_game("FC Test", "Foo", 3, 0);
+        test_table.add_game("Bar", "Unknown", 3, 0);
+
+        assert!(test_table.ratings.rating("FC Test") > test_table.ratings.rating("Bar"));
+        assert!(test_table.ratings.rating("Foo") < test_table.ratings.rating("Bar"));
+        assert_eq!(test_table.ratings.history.len(), 2);
+    }
+
+    #[test]
+    fn test_add_match_result_types() {
+        let team_names = vec![
+            String::from("FC Test"),
+            String::from("Foo"),
+            String::from("Bar"),
+        ];
+        let qualified = |home: &str, away: &str, home_score, away_score, result_type| Match {
+            result_type,
+            ..matches::create_match(home, away, home_score, away_score, 1)
+        };
+
+        let mut test_table = create_table(&team_names);
+        test_table.add_match(qualified(
+            "FC Test",
+            "Foo",
+            3,
+            0,
+            matches::ResultType::Walkover,
+        ));
+        test_table.add_match(qualified(
+            "Foo",
+            "Bar",
+            2,
+            2,
+            matches::ResultType::Penalties(4, 2),
+        ));
+        test_table.add_match(qualified(
+            "Bar",
+            "FC Test",
+            1,
+            0,
+            matches::ResultType::Abandoned,
+        ));
+        test_table.add_match(qualified(
+            "FC Test",
+            "Bar",
+            2,
+            0,
+            matches::ResultType::Annulled,
+        ));
+        test_table.update_table();
+
+        assert_eq!(test_table.teams[0].name, "FC Test");
+        assert_eq!(test_table.teams[0].points, 3);
+        assert_eq!(test_table.teams[0].played, 1);
+        assert_eq!(test_table.teams[1].points, 1);
+        assert_eq!(test_table.ratings.history.len(), 2);
+        assert_eq!(test_table.form("FC Test", 5, None), "W");
+
+        // The abandoned match is replayed, the annulled one is not.
+        let remaining = test_table.remaining_fixtures().unwrap();
+        assert!(remaining.contains(&fixtures::create_fixture("Bar", "FC Test")));
+        assert!(!remaining.contains(&fixtures::create_fixture("FC Test", "Bar")));
+    }
+
+    #[test]
+    fn test_remaining_fixtures_without_match_log() {
+        let team_names = vec![String::from("FC Test"), String::from("Foo")];
+
+        // Records read from a table file, with the season schedule from a fixtures file.
+        let mut test_table = create_table(&team_names);
+        test_table.fixtures = fixtures::double_round_robin(&team_names);
+        test_table.teams[0].wins = 1;
+        test_table.teams[1].defeats = 1;
+        test_table.update_table();
+
+        assert!(!test_table.has_known_schedule());
+        assert!(test_table.remaining_fixtures().is_err());
+        assert_eq!(test_table.remaining_games("FC Test"), 1);
+
+        // With the match behind the records logged the schedule tells what is left.
+        test_table.teams = create_table(&team_names).teams;
+        test_table.add_game("FC Test", "Foo", 1, 0);
+        assert_eq!(test_table.remaining_fixtures().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_games_in_hand_without_match_log() {
+        let team_names = vec![
+            String::from("FC Test"),
+            String::from("Foo"),
+            String::from("Bar"),
+        ];
+
+        let mut test_table = create_table(&team_names);
+        test_table.fixtures = fixtures::double_round_robin(&team_names);
+        test_table.teams[0].wins = 2;
+        test_table.teams[1].defeats = 1;
+        test_table.teams[2].defeats = 1;
+        test_table.update_table();
+
+        assert_eq!(test_table.games_in_hand("Foo"), 0);
+        assert_eq!(
+            test_table.postpone("FC Test", "Foo", "Frozen pitch"),
+            Err(UNKNOWN_SCHEDULE)
+        );
+        assert!(test_table
+            .fixtures
+            .iter()
+            .all(|fixture| fixture.postponement.is_none()));
+    }
+
+    #[test]
+    fn test_postpone_and_reschedule() {
+        let team_names = vec![
+            String::from("FC Test"),
+            String::from("Foo"),
+            String::from("Bar"),
+        ];
+
+        let mut test_table = create_table(&team_names);
+        test_table.add_game("Foo", "Bar", 1, 0);
+        test_table
+            .postpone("FC Test", "Foo", "Frozen pitch")
+            .unwrap();
+        assert_eq!(
+            test_table.postpone("FC Test", "Foo", "Snow"),
+            Err("The fixture is already postponed.")
+        );
+        assert_eq!(
+            test_table.postpone("Foo", "Bar", "Snow"),
+            Err("The fixture is not in the remaining schedule.")
+        );
+        assert_eq!(test_table.games_in_hand("FC Test"), 1);
+        assert_eq!(test_table.games_in_hand("Bar"), 0);
+
+        let kick_off = calendar::parse_kick_off("2024-05-02 19:00").unwrap();
+        test_table.reschedule("FC Test", "Foo", kick_off).unwrap();
+        assert_eq!(
+            test_table.reschedule("Bar", "Foo", kick_off),
+            Err("The fixture has not been postponed.")
+        );
+        let rescheduled = test_table
+            .remaining_fixtures()
+            .unwrap()
+            .into_iter()
+            .find(|fixture| fixture.home_team == "FC Test" && fixture.away_team == "Foo")
+            .unwrap();
+        assert_eq!(rescheduled.kick_off, Some(kick_off));
+
+        test_table.add_game("FC Test", "Foo", 2, 2);
+        assert_eq!(test_table.games_in_hand("FC Test"), 0);
+    }
 }
@@ -1,21 +1,57 @@
-#[derive(Default, Debug)]
+use crate::standings::matches::Venue;
+
+// The part of a team's record from the matches it played at one venue.
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct VenueRecord {
+    pub wins: u32,
+    pub draws: u32,
+    pub defeats: u32,
+    pub goal_scored: i32,
+    pub goal_against: i32,
+}
+
+impl VenueRecord {
+    pub fn played(&self) -> u32 {
+        self.wins + self.draws + self.defeats
+    }
+
+    fn add_result(&mut self, goal_scored: i32, goal_conceded: i32) {
+        if goal_scored > goal_conceded {
+            self.wins += 1;
+        } else if goal_scored < goal_conceded {
+            self.defeats += 1;
+        } else {
+            self.draws += 1;
+        }
+        self.goal_scored += goal_scored;
+        self.goal_against += goal_conceded;
+    }
+}
+
+#[derive(Clone, Default, Debug)]
 pub struct TeamStats {
     pub name: String,
-    pub played: u8,
-    pub wins: u8,
-    pub draws: u8,
-    pub defeats: u8,
+    pub played: u32,
+    pub wins: u32,
+    pub draws: u32,
+    pub defeats: u32,
     pub goal_scored: i32,
     pub goal_against: i32,
     pub goal_difference: i32,
-    pub points: u8,
+    pub points: u32,
+    // Added to the points from results, e.g. negative when points are halved at a split.
+    pub points_adjustment: i32,
+    // Home and away records of the results added with `add_venue_result`. Records read from a
+    // table file are not split by venue.
+    pub home: VenueRecord,
+    pub away: VenueRecord,
 }
 
 impl TeamStats {
     pub fn calc_stats(&mut self) {
         self.played = self.wins + self.draws + self.defeats;
         self.goal_difference = self.goal_scored - self.goal_against;
-        self.points = self.wins * 3 + self.draws;
+        self.points = (self.wins * 3 + self.draws).saturating_add_signed(self.points_adjustment);
     }
 
     fn update_goals(&mut self, goal_scored: i32, goal_conceded: i32) {
@@ -37,6 +73,32 @@ impl TeamStats {
         self.defeats += 1;
         self.update_goals(goal_scored, goal_conceded);
     }
+
+    pub fn add_result(&mut self, goal_scored: i32, goal_conceded: i32) {
+        if goal_scored > goal_conceded {
+            self.add_win(goal_scored, goal_conceded);
+        } else if goal_scored < goal_conceded {
+            self.add_defeat(goal_scored, goal_conceded);
+        } else {
+            self.add_draw(goal_scored, goal_conceded);
+        }
+    }
+
+    // Adds a result to the team record and to the record of the venue it was played at.
+    pub fn add_venue_result(&mut self, venue: Venue, goal_scored: i32, goal_conceded: i32) {
+        self.add_result(goal_scored, goal_conceded);
+        match venue {
+            Venue::Home => self.home.add_result(goal_scored, goal_conceded),
+            Venue::Away => self.away.add_result(goal_scored, goal_conceded),
+        }
+    }
+
+    pub fn venue_record(&self, venue: Venue) -> VenueRecord {
+        match venue {
+            Venue::Home => self.home,
+            Venue::Away => self.away,
+        }
+    }
 }
 
 pub fn create_team_template() -> TeamStats {
@@ -50,10 +112,13 @@ pub fn create_team_template() -> TeamStats {
         goal_against: 0,
         goal_difference: 0,
         points: 0,
+        points_adjustment: 0,
+        home: VenueRecord::default(),
+        away: VenueRecord::default(),
     }
 }
 
-pub fn create_team(team_str: &String) -> Result<TeamStats, &'static str> {
+pub fn create_team(team_str: &str) -> Result<TeamStats, &'static str> {
     let parts: Vec<&str> = team_str.split(";").collect();
 
     if parts.len() != 7 {
@@ -63,19 +128,19 @@ pub fn create_team(team_str: &String) -> Result<TeamStats, &'static str> {
 
         let played = parts[1]
             .trim()
-            .parse::<u8>()
+            .parse::<u32>()
             .map_err(|_| "Could not parse number of played games.")?;
         let wins = parts[2]
             .trim()
-            .parse::<u8>()
+            .parse::<u32>()
             .map_err(|_| "Could not parse number of wins.")?;
         let draws = parts[3]
             .trim()
-            .parse::<u8>()
+            .parse::<u32>()
             .map_err(|_| "Could not parse number of draws.")?;
         let defeats = parts[4]
             .trim()
-            .parse::<u8>()
+            .parse::<u32>()
             .map_err(|_| "Could not parse number of defeats.")?;
         let goal_scored = parts[5]
             .trim()
@@ -87,11 +152,11 @@ pub fn create_team(team_str: &String) -> Result<TeamStats, &'static str> {
             .map_err(|_| "Could not parse number of conceded goals.")?;
         Ok(TeamStats {
             name: team_name.to_string(),
-            played: played,
-            wins: wins,
-            draws: draws,
-            defeats: defeats,
-            goal_scored: goal_scored,
+            played,
+            wins,
+            draws,
+            defeats,
+            goal_scored,
             goal_against: goal_conceded,
             ..create_team_template()
         })
@@ -185,6 +250,20 @@ mod tests {
         assert_eq!(parsed_team.goal_difference, 8);
     }
 
+    #[test]
+    fn test_add_venue_result() {
+        let mut template = create_team_template();
+        template.add_venue_result(Venue::Home, 2, 0);
+        template.add_venue_result(Venue::Away, 1, 1);
+        template.calc_stats();
+
+        assert_eq!(template.points, 4);
+        assert_eq!(template.home.wins, 1);
+        assert_eq!(template.home.goal_scored, 2);
+        assert_eq!(template.venue_record(Venue::Away).draws, 1);
+        assert_eq!(template.away.played(), 1);
+    }
+
     #[test]
     #[should_panic(expected = "Could not parse number of played games.")]
     fn test_create_team_panic_incorrect_played() {
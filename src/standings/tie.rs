@@ -0,0 +1,356 @@
+use crate::standings::matches::{Match, ResultType};
+
+// How a knockout tie is decided when the teams are level on aggregate.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TieRules {
+    pub legs: usize,
+    // Goals scored away count double once the aggregate is level. Only used over two legs.
+    pub away_goals: bool,
+    pub extra_time: bool,
+    pub penalties: bool,
+}
+
+impl Default for TieRules {
+    // Two legs with extra time and penalties in the second leg, like UEFA ties since 2021.
+    fn default() -> TieRules {
+        TieRules {
+            legs: 2,
+            away_goals: false,
+            extra_time: true,
+            penalties: true,
+        }
+    }
+}
+
+impl TieRules {
+    pub fn single_match() -> TieRules {
+        TieRules {
+            legs: 1,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Decider {
+    Aggregate,
+    AwayGoals,
+    ExtraTime,
+    Penalties,
+}
+
+impl Decider {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Decider::Aggregate => "on aggregate",
+            Decider::AwayGoals => "on away goals",
+            Decider::ExtraTime => "after extra time",
+            Decider::Penalties => "on penalties",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TieOutcome {
+    // More legs, extra time or a shootout are still to be played.
+    Undecided,
+    Winner(String, Decider),
+}
+
+// A knockout tie between the home team of the first leg (team 1) and its away team (team 2).
+// Every further leg swaps the venues.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tie {
+    pub legs: Vec<Match>,
+    // Goals scored in extra time of the last leg, as home and away goals of that leg. Not set
+    // when the last leg was entered as "aet" or with a shootout, since its score then already
+    // holds the goals of extra time.
+    pub extra_time: Option<(i32, i32)>,
+    // Shootout score of the last leg, as home and away.
+    pub penalties: Option<(i32, i32)>,
+}
+
+impl Tie {
+    pub fn team1(&self) -> &str {
+        &self.legs[0].home_team
+    }
+
+    pub fn team2(&self) -> &str {
+        &self.legs[0].away_team
+    }
+
+    // Adds the next leg, which must be played between the same teams with swapped venues.
+    pub fn add_leg(&mut self, leg: Match) -> Result<(), &'static str> {
+        let previous = self.legs.last().ok_or("The tie has no first leg.")?;
+        if leg.home_team != previous.away_team || leg.away_team != previous.home_team {
+            return Err("The next leg must be played between the same teams with venues swapped.");
+        }
+
+        self.legs.push(leg);
+        Ok(())
+    }
+
+    // Goals of team 1 and team 2 over all legs, with extra time when `with_extra_time`.
+    pub fn aggregate(&self, with_extra_time: bool) -> (i32, i32) {
+        let mut aggregate = self.legs.iter().fold((0, 0), |(team1, team2), leg| {
+            let (scored, conceded) = leg.score_for(self.team1()).unwrap_or((0, 0));
+            (team1 + scored, team2 + conceded)
+        });
+
+        if let (true, Some(extra_time)) = (with_extra_time, self.extra_time) {
+            let (team1, team2) = self.last_leg_split(extra_time);
+            aggregate.0 += team1;
+            aggregate.1 += team2;
+        }
+        aggregate
+    }
+
+    // Goals team 1 and team 2 scored away from home, with extra time when `with_extra_time`.
+    pub fn away_goals(&self, with_extra_time: bool) -> (i32, i32) {
+        let mut away_goals = self.legs.iter().fold((0, 0), |(team1, team2), leg| {
+            if leg.away_team == self.team1() {
+                (team1 + leg.away_score, team2)
+            } else {
+                (team1, team2 + leg.away_score)
+            }
+        });
+
+        if let (true, Some((_, away_score))) = (with_extra_time, self.extra_time) {
+            if self.legs[self.legs.len() - 1].away_team == self.team1() {
+                away_goals.0 += away_score;
+            } else {
+                away_goals.1 += away_score;
+            }
+        }
+        away_goals
+    }
+
+    // Whether the last leg went to extra time, entered separately or as part of its result.
+    pub fn went_to_extra_time(&self) -> bool {
+        self.extra_time.is_some() || self.last_leg_includes_extra_time()
+    }
+
+    // A last leg entered as e.g. "2-1 aet" or "1-1 (5-4 p)": the tie was level after 90
+    // minutes, which is all that is known about the score before extra time.
+    fn last_leg_includes_extra_time(&self) -> bool {
+        self.legs.last().is_some_and(|leg| {
+            matches!(
+                leg.result_type,
+                ResultType::AfterExtraTime | ResultType::Penalties(..)
+            )
+        })
+    }
+
+    pub fn outcome(&self, rules: &TieRules) -> TieOutcome {
+        if self.legs.len() < rules.legs {
+            return TieOutcome::Undecided;
+        }
+
+        let away_goals = rules.away_goals && rules.legs > 1;
+        let includes_extra_time = self.last_leg_includes_extra_time();
+        if !includes_extra_time {
+            if let Some(winner) = self.leader(false, away_goals) {
+                let decider = if self.aggregate(false).0 == self.aggregate(false).1 {
+                    Decider::AwayGoals
+                } else {
+                    Decider::Aggregate
+                };
+                return TieOutcome::Winner(winner, decider);
+            }
+        }
+
+        if rules.extra_time || includes_extra_time {
+            if !self.went_to_extra_time() {
+                return TieOutcome::Undecided;
+            }
+            if let Some(winner) = self.leader(true, away_goals) {
+                return TieOutcome::Winner(winner, Decider::ExtraTime);
+            }
+        }
+
+        match (rules.penalties, self.penalties) {
+            (true, Some(penalties)) if penalties.0 != penalties.1 => {
+                let (team1, team2) = self.last_leg_split(penalties);
+                let winner = if team1 > team2 {
+                    self.team1()
+                } else {
+                    self.team2()
+                };
+                TieOutcome::Winner(winner.to_string(), Decider::Penalties)
+            }
+            _ => TieOutcome::Undecided,
+        }
+    }
+
+    pub fn print(&self, rules: &TieRules) {
+        self.legs
+            .iter()
+            .enumerate()
+            .for_each(|(index, leg)| println!("Leg {}: {}", index + 1, leg));
+        if let Some((home_score, away_score)) = self.extra_time {
+            println!("Extra time: {}-{}", home_score, away_score);
+        }
+        if let Some((home_score, away_score)) = self.penalties {
+            println!("Penalties: {}-{}", home_score, away_score);
+        }
+
+        let (team1, team2) = self.aggregate(true);
+        println!(
+            "Aggregate: {} {}-{} {}",
+            self.team1(),
+            team1,
+            team2,
+            self.team2()
+        );
+        match self.outcome(rules) {
+            TieOutcome::Winner(winner, decider) => {
+                println!("{} go through {}.", winner, decider.name())
+            }
+            TieOutcome::Undecided => println!("The tie is not decided yet."),
+        }
+    }
+
+    // The team ahead on aggregate, then on away goals if they count.
+    fn leader(&self, with_extra_time: bool, away_goals: bool) -> Option<String> {
+        let (mut team1, mut team2) = self.aggregate(with_extra_time);
+        if team1 == team2 && away_goals {
+            (team1, team2) = self.away_goals(with_extra_time);
+        }
+
+        if team1 > team2 {
+            Some(self.team1().to_string())
+        } else if team2 > team1 {
+            Some(self.team2().to_string())
+        } else {
+            None
+        }
+    }
+
+    // Splits a home-away score of the last leg into team 1 and team 2.
+    fn last_leg_split(&self, (home_score, away_score): (i32, i32)) -> (i32, i32) {
+        if self.legs[self.legs.len() - 1].home_team == self.team1() {
+            (home_score, away_score)
+        } else {
+            (away_score, home_score)
+        }
+    }
+}
+
+pub fn create_tie(first_leg: Match) -> Tie {
+    Tie {
+        legs: vec![first_leg],
+        extra_time: None,
+        penalties: None,
+    }
+}
+
+// Parses "x-y" as used for extra time and penalty scores.
+pub fn parse_score(score_str: &str) -> Result<(i32, i32), &'static str> {
+    let score: Vec<&str> = score_str.split('-').collect();
+    if score.len() != 2 {
+        return Err("Invalid score format.");
+    }
+
+    let home_score = score[0]
+        .trim()
+        .parse::<i32>()
+        .map_err(|_| "Cannot parse home team score.")?;
+    let away_score = score[1]
+        .trim()
+        .parse::<i32>()
+        .map_err(|_| "Cannot parse the away team score.")?;
+    Ok((home_score, away_score))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standings::matches;
+
+    fn create_test_tie(first: (i32, i32), second: (i32, i32)) -> Tie {
+        let mut tie = create_tie(matches::create_match("Foo", "Bar", first.0, first.1, 1));
+        tie.add_leg(matches::create_match("Bar", "Foo", second.0, second.1, 2))
+            .unwrap();
+        tie
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let tie = create_test_tie((2, 1), (1, 0));
+
+        assert_eq!(tie.aggregate(false), (2, 2));
+        assert_eq!(tie.away_goals(false), (0, 1));
+        assert_eq!(tie.outcome(&TieRules::default()), TieOutcome::Undecided);
+
+        let away_goals = TieRules {
+            away_goals: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            tie.outcome(&away_goals),
+            TieOutcome::Winner(String::from("Bar"), Decider::AwayGoals)
+        );
+    }
+
+    #[test]
+    fn test_extra_time_and_penalties() {
+        let mut tie = create_test_tie((1, 0), (1, 0));
+        assert_eq!(tie.outcome(&TieRules::default()), TieOutcome::Undecided);
+
+        tie.extra_time = Some((0, 0));
+        assert_eq!(tie.outcome(&TieRules::default()), TieOutcome::Undecided);
+
+        tie.penalties = Some((4, 5));
+        assert_eq!(
+            tie.outcome(&TieRules::default()),
+            TieOutcome::Winner(String::from("Foo"), Decider::Penalties)
+        );
+
+        tie.extra_time = Some((2, 1));
+        assert_eq!(tie.aggregate(true), (2, 3));
+        assert_eq!(
+            tie.outcome(&TieRules::default()),
+            TieOutcome::Winner(String::from("Bar"), Decider::ExtraTime)
+        );
+    }
+
+    #[test]
+    fn test_second_leg_after_extra_time() {
+        let mut tie = create_tie(matches::create_match("Foo", "Bar", 1, 0, 1));
+        tie.add_leg(Match {
+            result_type: ResultType::AfterExtraTime,
+            ..matches::create_match("Bar", "Foo", 2, 0, 2)
+        })
+        .unwrap();
+
+        // Bar only lead on aggregate with the goals of extra time in the second leg score.
+        assert!(tie.went_to_extra_time());
+        assert_eq!(tie.aggregate(true), (1, 2));
+        assert_eq!(
+            tie.outcome(&TieRules::default()),
+            TieOutcome::Winner(String::from("Bar"), Decider::ExtraTime)
+        );
+    }
+
+    #[test]
+    fn test_single_match() {
+        let tie = create_tie(matches::create_match("Foo", "Bar", 0, 3, 1));
+
+        assert_eq!(
+            tie.outcome(&TieRules::single_match()),
+            TieOutcome::Winner(String::from("Bar"), Decider::Aggregate)
+        );
+        assert_eq!(tie.outcome(&TieRules::default()), TieOutcome::Undecided);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "The next leg must be played between the same teams with venues swapped."
+    )]
+    fn test_add_leg_panic_wrong_teams() {
+        let mut tie = create_tie(matches::create_match("Foo", "Bar", 0, 3, 1));
+
+        tie.add_leg(matches::create_match("Foo", "Bar", 1, 1, 2))
+            .unwrap();
+    }
+}
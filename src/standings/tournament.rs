@@ -0,0 +1,452 @@
+use crate::standings::cup;
+use crate::standings::cup::Cup;
+use crate::standings::fixtures;
+use crate::standings::matches::Match;
+use crate::standings::tables;
+use crate::standings::tables::Table;
+use crate::standings::team::TeamStats;
+
+// Criteria that separate teams level on points, applied in order.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tiebreaker {
+    GoalDifference,
+    GoalsScored,
+    // Points in the matches between the teams level on points.
+    HeadToHead,
+}
+
+// A group plays a single round robin. Its table is ranked with its own tiebreakers instead of
+// `Table::update_table`.
+#[derive(Clone, Debug)]
+pub struct Group {
+    pub table: Table,
+    pub tiebreakers: Vec<Tiebreaker>,
+}
+
+impl Group {
+    pub fn standings(&self) -> Vec<TeamStats> {
+        let mut teams = self.table.teams.clone();
+        teams.iter_mut().for_each(|team| team.calc_stats());
+
+        let mut ranked: Vec<(Vec<i32>, TeamStats)> = teams
+            .iter()
+            .map(|team| (self.ranking_key(team, &teams), team.clone()))
+            .collect();
+        ranked.sort_by(|(key1, _), (key2, _)| key2.cmp(key1));
+        ranked.into_iter().map(|(_, team)| team).collect()
+    }
+
+    fn ranking_key(&self, team: &TeamStats, teams: &[TeamStats]) -> Vec<i32> {
+        let mut key = vec![team.points as i32];
+
+        for tiebreaker in &self.tiebreakers {
+            key.push(match tiebreaker {
+                Tiebreaker::GoalDifference => team.goal_difference,
+                Tiebreaker::GoalsScored => team.goal_scored,
+                Tiebreaker::HeadToHead => {
+                    let level: Vec<&str> = teams
+                        .iter()
+                        .filter(|other| other.points == team.points)
+                        .map(|other| other.name.as_str())
+                        .collect();
+                    self.table
+                        .matches
+                        .iter()
+                        .filter(|game| {
+                            level.contains(&game.home_team.as_str())
+                                && level.contains(&game.away_team.as_str())
+                        })
+                        .filter_map(|game| game.score_for(&team.name))
+                        .map(|(scored, conceded)| match scored.cmp(&conceded) {
+                            std::cmp::Ordering::Greater => 3,
+                            std::cmp::Ordering::Equal => 1,
+                            std::cmp::Ordering::Less => 0,
+                        })
+                        .sum()
+                }
+            });
+        }
+
+        key
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Tournament {
+    pub name: String,
+    pub groups: Vec<Group>,
+    // Teams from the top of every group that go through.
+    pub qualifiers_per_group: usize,
+    // Additional teams from the next position, the best across all groups.
+    pub best_next_placed: usize,
+    pub knockout: Option<Cup>,
+}
+
+impl Tournament {
+    // Adds a group result to the group both teams play in, if the fixture is still open.
+    pub fn add_result(&mut self, game: Match) -> Result<(), &'static str> {
+        let group = self
+            .groups
+            .iter_mut()
+            .find(|group| {
+                group.table.position(&game.home_team).is_some()
+                    && group.table.position(&game.away_team).is_some()
+            })
+            .ok_or("The teams do not play in the same group.")?;
+
+        if !group
+            .table
+            .remaining_fixtures()?
+            .iter()
+            .any(|fixture| fixture.is_played_as(&game))
+        {
+            return Err("The fixture has already been played or is not scheduled.");
+        }
+
+        group.table.add_match(game);
+        Ok(())
+    }
+
+    pub fn group_stage_finished(&self) -> bool {
+        self.groups.iter().all(|group| {
+            group
+                .table
+                .remaining_fixtures()
+                .is_ok_and(|remaining| remaining.is_empty())
+        })
+    }
+
+    // The teams in the first position after the qualifiers of every group, best first, ranked
+    // on points, goal difference and goals scored.
+    pub fn next_placed_ranking(&self) -> Vec<TeamStats> {
+        let mut next_placed: Vec<TeamStats> = self
+            .groups
+            .iter()
+            .filter_map(|group| group.standings().get(self.qualifiers_per_group).cloned())
+            .collect();
+        next_placed.sort_by_key(|team| {
+            std::cmp::Reverse((team.points, team.goal_difference, team.goal_scored))
+        });
+        next_placed
+    }
+
+    // The knockout entrants in seeding order: all group winners, then all runners-up and so
+    // on, followed by the best next placed teams. The order is then adjusted so that the
+    // seeded draw keeps teams of the same group apart, see `keep_groups_apart`.
+    pub fn qualifiers(&self) -> Vec<String> {
+        let standings: Vec<Vec<TeamStats>> =
+            self.groups.iter().map(|group| group.standings()).collect();
+
+        let mut qualifiers = Vec::new();
+        for position in 0..self.qualifiers_per_group {
+            let mut placed: Vec<(&TeamStats, usize)> = standings
+                .iter()
+                .enumerate()
+                .filter_map(|(group, group_standings)| {
+                    group_standings.get(position).map(|team| (team, group))
+                })
+                .collect();
+            placed.sort_by_key(|(team, _)| {
+                std::cmp::Reverse((team.points, team.goal_difference, team.goal_scored))
+            });
+            qualifiers.extend(
+                placed
+                    .iter()
+                    .map(|(team, group)| (team.name.clone(), *group)),
+            );
+        }
+        qualifiers.extend(
+            self.next_placed_ranking()
+                .iter()
+                .take(self.best_next_placed)
+                .map(|team| (team.name.clone(), self.group_of(&team.name))),
+        );
+        keep_groups_apart(qualifiers)
+    }
+
+    fn group_of(&self, team_name: &str) -> usize {
+        self.groups
+            .iter()
+            .position(|group| group.table.position(team_name).is_some())
+            .unwrap_or(usize::MAX)
+    }
+
+    // Seeds the knockout from the group positions and draws its first round.
+    pub fn start_knockout(&mut self) -> Result<(), &'static str> {
+        if self.knockout.is_some() {
+            return Err("The knockout stage has already started.");
+        }
+        if !self.group_stage_finished() {
+            return Err("The group stage is not finished.");
+        }
+
+        let mut knockout = cup::create_cup(&self.name, &self.qualifiers());
+        knockout.draw(cup::DrawMode::Seeded)?;
+        self.knockout = Some(knockout);
+        Ok(())
+    }
+
+    pub fn print_groups(&self) {
+        for group in &self.groups {
+            let mut ranked = Table {
+                teams: group.standings(),
+                ..group.table.clone()
+            };
+            let status: Vec<String> = (0..ranked.teams.len())
+                .map(|index| {
+                    if index < self.qualifiers_per_group {
+                        String::from("Q")
+                    } else {
+                        String::new()
+                    }
+                })
+                .collect();
+            ranked.print_with_columns(&[("", status)]);
+        }
+
+        if self.best_next_placed > 0 {
+            println!(
+                "Best teams in position {} (top {} go through)",
+                self.qualifiers_per_group + 1,
+                self.best_next_placed
+            );
+            for (index, team) in self.next_placed_ranking().iter().enumerate() {
+                println!(
+                    "  {}. {} {}p {:+} {}",
+                    index + 1,
+                    team.name,
+                    team.points,
+                    team.goal_difference,
+                    team.goal_scored
+                );
+            }
+        }
+    }
+}
+
+// Reorders entrants given in seeding order with their group. The seeded draw gives the top
+// seeds their byes and pairs the best remaining seed with the worst. Here every team of the top
+// half is instead given the lowest seed of the bottom half from another group, as in the
+// usual A1-B2, B1-A2 layout. Teams of the same group only meet when no other team is left.
+fn keep_groups_apart(entrants: Vec<(String, usize)>) -> Vec<String> {
+    let byes = entrants.len().next_power_of_two() - entrants.len();
+    let (with_byes, playing) = entrants.split_at(byes);
+    let (top, bottom) = playing.split_at(playing.len() / 2);
+
+    let mut unpaired: Vec<&(String, usize)> = bottom.iter().collect();
+    let mut opponents = Vec::new();
+    for (_, group) in top {
+        let index = unpaired
+            .iter()
+            .rposition(|(_, other_group)| other_group != group)
+            .unwrap_or(unpaired.len() - 1);
+        opponents.push(unpaired.remove(index));
+    }
+
+    with_byes
+        .iter()
+        .chain(top)
+        .chain(unpaired)
+        .chain(opponents.into_iter().rev())
+        .map(|(name, _)| name.clone())
+        .collect()
+}
+
+// Groups are named "Group A", "Group B" and so on. Every group starts with the tiebreakers
+// goal difference, goals scored and head-to-head.
+pub fn create_tournament(
+    name: &str,
+    groups: &[Vec<String>],
+    qualifiers_per_group: usize,
+    best_next_placed: usize,
+) -> Tournament {
+    let groups = groups
+        .iter()
+        .enumerate()
+        .map(|(index, teams)| {
+            let mut table = tables::create_table(teams);
+            table.name = format!("Group {}", (b'A' + index as u8) as char);
+            table.fixtures = fixtures::single_round_robin(teams);
+            Group {
+                table,
+                tiebreakers: vec![
+                    Tiebreaker::GoalDifference,
+                    Tiebreaker::GoalsScored,
+                    Tiebreaker::HeadToHead,
+                ],
+            }
+        })
+        .collect();
+
+    Tournament {
+        name: name.to_string(),
+        groups,
+        qualifiers_per_group,
+        best_next_placed,
+        knockout: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standings::matches;
+
+    fn names(teams: &[&str]) -> Vec<String> {
+        teams.iter().map(|team| team.to_string()).collect()
+    }
+
+    fn play_group(tournament: &mut Tournament, results: &[(&str, &str, i32, i32)]) {
+        for (home_team, away_team, home_score, away_score) in results {
+            tournament
+                .add_result(matches::create_match(
+                    home_team,
+                    away_team,
+                    *home_score,
+                    *away_score,
+                    1,
+                ))
+                .unwrap();
+        }
+    }
+
+    fn create_test_tournament() -> Tournament {
+        let mut tournament = create_tournament(
+            "Test Cup",
+            &[names(&["A1", "A2", "A3"]), names(&["B1", "B2", "B3"])],
+            1,
+            1,
+        );
+        play_group(
+            &mut tournament,
+            &[
+                ("A1", "A2", 1, 0),
+                ("A1", "A3", 2, 0),
+                ("A2", "A3", 3, 0),
+                ("B1", "B2", 0, 0),
+                ("B1", "B3", 2, 0),
+                ("B2", "B3", 1, 0),
+            ],
+        );
+        tournament
+    }
+
+    #[test]
+    fn test_single_round_robin_groups() {
+        let tournament = create_test_tournament();
+
+        assert_eq!(tournament.groups[1].table.name, "Group B");
+        assert_eq!(tournament.groups[1].table.fixtures.len(), 3);
+        assert!(tournament.group_stage_finished());
+    }
+
+    #[test]
+    fn test_add_result() {
+        let mut tournament = create_test_tournament();
+
+        assert_eq!(
+            tournament.add_result(matches::create_match("A1", "B1", 1, 0, 2)),
+            Err("The teams do not play in the same group.")
+        );
+        assert_eq!(
+            tournament.add_result(matches::create_match("A2", "A1", 1, 0, 2)),
+            Err("The fixture has already been played or is not scheduled.")
+        );
+    }
+
+    #[test]
+    fn test_head_to_head_tiebreaker() {
+        let mut tournament = create_tournament("Test Cup", &[names(&["X", "Y", "Z", "W"])], 2, 0);
+        play_group(
+            &mut tournament,
+            &[
+                ("X", "Y", 1, 0),
+                ("X", "Z", 0, 1),
+                ("X", "W", 1, 0),
+                ("Y", "Z", 5, 0),
+                ("Y", "W", 5, 0),
+                ("Z", "W", 0, 0),
+            ],
+        );
+
+        // X and Y have six points each, Y with the better goal difference but X won the match
+        // between them.
+        let standings = tournament.groups[0].standings();
+        assert_eq!(standings[0].name, "Y");
+        assert_eq!(standings[1].name, "X");
+
+        tournament.groups[0].tiebreakers = vec![Tiebreaker::HeadToHead, Tiebreaker::GoalDifference];
+        let standings = tournament.groups[0].standings();
+        assert_eq!(standings[0].name, "X");
+        assert_eq!(standings[1].name, "Y");
+        assert_eq!(standings[2].name, "Z");
+    }
+
+    #[test]
+    fn test_qualifiers_and_knockout() {
+        let mut tournament = create_test_tournament();
+
+        // Both runners-up are ranked, B2 has four points to A2's three.
+        assert_eq!(tournament.next_placed_ranking()[0].name, "B2");
+        assert_eq!(tournament.qualifiers(), names(&["A1", "B1", "B2"]));
+
+        tournament.start_knockout().unwrap();
+        let knockout = tournament.knockout.as_ref().unwrap();
+        assert_eq!(knockout.rounds[0][0].home_team, "A1");
+        assert_eq!(knockout.rounds[0][0].away_team, None);
+        assert_eq!(knockout.rounds[0][1].home_team, "B1");
+        assert_eq!(knockout.rounds[0][1].away_team, Some(String::from("B2")));
+        assert_eq!(
+            tournament.start_knockout(),
+            Err("The knockout stage has already started.")
+        );
+    }
+
+    #[test]
+    fn test_knockout_keeps_groups_apart() {
+        let mut tournament = create_tournament(
+            "Test Cup",
+            &[names(&["A1", "A2", "A3"]), names(&["B1", "B2", "B3"])],
+            2,
+            0,
+        );
+        play_group(
+            &mut tournament,
+            &[
+                ("A1", "A2", 3, 0),
+                ("A1", "A3", 3, 0),
+                ("A2", "A3", 1, 1),
+                ("B1", "B2", 3, 0),
+                ("B1", "B3", 2, 0),
+                ("B2", "B3", 2, 0),
+            ],
+        );
+
+        // A1 is the best winner and B2, with three points to A2's one, the best runner-up. A
+        // draw by merit alone would pair A1 with A2 and B1 with B2.
+        assert_eq!(tournament.qualifiers(), names(&["A1", "B1", "A2", "B2"]));
+
+        tournament.start_knockout().unwrap();
+        let first_round = &tournament.knockout.as_ref().unwrap().rounds[0];
+        assert_eq!(first_round[0].home_team, "A1");
+        assert_eq!(first_round[0].away_team, Some(String::from("B2")));
+        assert_eq!(first_round[1].home_team, "B1");
+        assert_eq!(first_round[1].away_team, Some(String::from("A2")));
+    }
+
+    #[test]
+    fn test_start_knockout_before_groups_finish() {
+        let mut tournament = create_tournament(
+            "Test Cup",
+            &[names(&["A1", "A2"]), names(&["B1", "B2"])],
+            1,
+            0,
+        );
+        play_group(&mut tournament, &[("A1", "A2", 1, 0)]);
+
+        assert_eq!(
+            tournament.start_knockout(),
+            Err("The group stage is not finished.")
+        );
+    }
+}
@@ -0,0 +1,75 @@
+// Table positions with a special outcome at the end of the season, counted from the top for
+// the title and Europe and from the bottom for the playoff and relegation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Zones {
+    pub title_spots: usize,
+    pub europe_spots: usize,
+    pub playoff_spots: usize,
+    pub relegation_spots: usize,
+}
+
+impl Default for Zones {
+    // Allsvenskan: the champion and two more teams qualify for Europe, 14th plays the
+    // relegation playoff and the bottom two go down.
+    fn default() -> Zones {
+        Zones {
+            title_spots: 1,
+            europe_spots: 3,
+            playoff_spots: 1,
+            relegation_spots: 2,
+        }
+    }
+}
+
+impl Zones {
+    // Last position that avoids both the playoff and relegation.
+    pub fn last_safe_position(&self, team_count: usize) -> usize {
+        team_count.saturating_sub(self.playoff_spots + self.relegation_spots)
+    }
+
+    // First position that means direct relegation.
+    pub fn first_relegation_position(&self, team_count: usize) -> usize {
+        team_count.saturating_sub(self.relegation_spots) + 1
+    }
+
+    // Zone of a 1-based table position, empty for mid-table.
+    pub fn label(&self, position: usize, team_count: usize) -> &'static str {
+        if position <= self.title_spots {
+            "Title"
+        } else if position <= self.europe_spots {
+            "Europe"
+        } else if position >= self.first_relegation_position(team_count) {
+            "Releg"
+        } else if position > self.last_safe_position(team_count) {
+            "Playoff"
+        } else {
+            ""
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allsvenskan_positions() {
+        let zones = Zones::default();
+
+        assert_eq!(zones.last_safe_position(16), 13);
+        assert_eq!(zones.first_relegation_position(16), 15);
+    }
+
+    #[test]
+    fn test_label() {
+        let zones = Zones::default();
+
+        assert_eq!(zones.label(1, 16), "Title");
+        assert_eq!(zones.label(3, 16), "Europe");
+        assert_eq!(zones.label(4, 16), "");
+        assert_eq!(zones.label(13, 16), "");
+        assert_eq!(zones.label(14, 16), "Playoff");
+        assert_eq!(zones.label(15, 16), "Releg");
+        assert_eq!(zones.label(16, 16), "Releg");
+    }
+}